@@ -0,0 +1,671 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::sync::Arc;
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+pub use self::gen_client::Client as PoeClient;
+
+/// The owner of a claim and the block it was created in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proof<AccountId, BlockNumber> {
+	/// The account that owns the claim.
+	pub owner: AccountId,
+	/// The block the claim was created in.
+	pub block_number: BlockNumber,
+}
+
+/// A page of the claims owned by an account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimsPage {
+	/// The SCALE-encoded hashes of the claims in this page.
+	pub claims: Vec<Bytes>,
+	/// The key to pass as `start_key` to get the next page, or `null` after the last page.
+	pub next_key: Option<Bytes>,
+}
+
+/// A page of the claims created in a range of blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimsRange<BlockNumber> {
+	/// The SCALE-encoded hashes of the claims in this page.
+	pub claims: Vec<Bytes>,
+	/// The block to pass as `from` to get the next page, or `null` after the last page.
+	pub next_block: Option<BlockNumber>,
+}
+
+/// Everything stored about a claim that a detail page shows.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimDetails<AccountId, BlockNumber> {
+	/// The account that owns the claim.
+	pub owner: AccountId,
+	/// The block the claim was created in.
+	pub created_at: BlockNumber,
+	/// The block the claim expires at, or `null` if it never expires.
+	pub expiry: Option<BlockNumber>,
+	/// The metadata attached to the claim, or `null` if there is none.
+	pub metadata: Option<Bytes>,
+	/// Whether the claim is frozen.
+	pub frozen: bool,
+	/// The block the claim is locked until, or `null` if it was never locked.
+	pub locked_until: Option<BlockNumber>,
+}
+
+/// The typed header stored with a claim.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimHeader {
+	/// The version of the proof format.
+	pub version: u16,
+	/// The kind of proof.
+	pub kind: u8,
+}
+
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber> {
+	/// Get the proof for the hex-encoded `claim`, or `null` if it does not exist.
+	#[rpc(name = "poe_getProof")]
+	fn get_proof(
+		&self,
+		claim: String,
+		at: Option<BlockHash>
+	) -> Result<Option<Proof<AccountId, BlockNumber>>>;
+
+	/// Get the proofs for each of the hex-encoded `claims`, in the same order, with `null` for
+	/// those that do not exist. At most `MaxQueryBatch` claims may be queried at once.
+	#[rpc(name = "poe_getProofs")]
+	fn get_proofs(
+		&self,
+		claims: Vec<String>,
+		at: Option<BlockHash>
+	) -> Result<Vec<Option<Proof<AccountId, BlockNumber>>>>;
+
+	/// Get up to `limit` claims owned by `owner`, starting at the hex-encoded `start_key`.
+	#[rpc(name = "poe_claimsOf")]
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		start_key: Option<String>,
+		limit: u32,
+		at: Option<BlockHash>
+	) -> Result<ClaimsPage>;
+
+	/// Get the SCALE-encoded hashes of the claims created from block `from` to block `to`, both
+	/// included, and the block the next page starts at. Claims removed since are listed too. A
+	/// page ends once it holds `limit` claims or `MAX_RANGE_SPAN` blocks were scanned.
+	#[rpc(name = "poe_claimsInRange")]
+	fn claims_in_range(
+		&self,
+		from: BlockNumber,
+		to: BlockNumber,
+		limit: u32,
+		at: Option<BlockHash>
+	) -> Result<ClaimsRange<BlockNumber>>;
+
+	/// Get the SCALE-encoded key that the hex-encoded `claim` is stored under.
+	#[rpc(name = "poe_deriveKey")]
+	fn derive_key(&self, claim: String, at: Option<BlockHash>) -> Result<Bytes>;
+
+	/// Get whether `who` is an owner of the hex-encoded `claim`. False if it does not exist.
+	#[rpc(name = "poe_isOwner")]
+	fn is_owner(&self, claim: String, who: AccountId, at: Option<BlockHash>) -> Result<bool>;
+
+	/// Check whether `who` could create the hex-encoded `claim` now, without submitting anything.
+	/// On failure, the result holds the index of the pallet error creating it would fail with.
+	#[rpc(name = "poe_canCreate")]
+	fn can_create(&self, claim: String, who: AccountId, at: Option<BlockHash>) -> Result<std::result::Result<(), u8>>;
+
+	/// Get everything stored about the hex-encoded `claim`, or `null` if it does not exist.
+	#[rpc(name = "poe_claimDetails")]
+	fn claim_details(
+		&self,
+		claim: String,
+		at: Option<BlockHash>
+	) -> Result<Option<ClaimDetails<AccountId, BlockNumber>>>;
+
+	/// Get the header stored with the hex-encoded `claim`, or `null` if it does not exist.
+	#[rpc(name = "poe_claimHeader")]
+	fn claim_header(&self, claim: String, at: Option<BlockHash>) -> Result<Option<ClaimHeader>>;
+
+	/// Get how many blocks ago the hex-encoded `claim` was created, or `null` if it does not
+	/// exist.
+	#[rpc(name = "poe_claimAge")]
+	fn claim_age(&self, claim: String, at: Option<BlockHash>) -> Result<Option<BlockNumber>>;
+
+	/// Get the number of claims on chain.
+	#[rpc(name = "poe_totalProofs")]
+	fn total_proofs(&self, at: Option<BlockHash>) -> Result<u32>;
+
+	/// Get the highest number of claims there have ever been on chain.
+	#[rpc(name = "poe_peakProofs")]
+	fn peak_proofs(&self, at: Option<BlockHash>) -> Result<u32>;
+
+	/// Get the number of claims owned by `owner`.
+	#[rpc(name = "poe_claimCountOf")]
+	fn claim_count_of(&self, owner: AccountId, at: Option<BlockHash>) -> Result<u32>;
+
+	/// Get how many accounts own each number of claims: bucket `i` counts the accounts owning
+	/// `i + 1` claims and the last bucket every account owning more. This iterates over every
+	/// account, so it is an unsafe method that nodes only serve when unsafe RPCs are allowed.
+	#[rpc(name = "poe_ownershipDistribution")]
+	fn ownership_distribution(&self, buckets: u32, at: Option<BlockHash>) -> Result<Vec<u32>>;
+}
+
+/// A struct that implements the [`PoeApi`].
+pub struct Poe<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Poe<C, B> {
+	/// Create new `Poe` with the given reference to the client, refusing unsafe methods unless
+	/// `deny_unsafe` allows them.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Poe { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> Poe<C, Block> where Block: BlockT, C: HeaderBackend<Block> {
+	/// Get the block to query at, which is the best block unless `at` is supplied.
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The claim was not valid hex.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+/// Turn the error of a failed runtime call into an RPC error, with `message` saying what failed.
+fn runtime_error(message: &str, error: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: message.into(),
+		data: Some(format!("{:?}", error).into()),
+	}
+}
+
+/// Decode a `0x`-prefixed hex string into the raw claim bytes.
+fn decode_claim(claim: &str) -> Result<Vec<u8>> {
+	decode_hex(claim, "claim")
+}
+
+/// Decode a `0x`-prefixed hex string, naming `what` was malformed in the error.
+fn decode_hex(hex: &str, what: &str) -> Result<Vec<u8>> {
+	sp_core::bytes::from_hex(hex).map_err(|e| RpcError {
+		code: ErrorCode::ServerError(Error::DecodeError.into()),
+		message: format!("Unable to decode {} as hex.", what),
+		data: Some(format!("{:?}", e).into()),
+	})
+}
+
+impl<C, Block, AccountId, BlockNumber> PoeApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for Poe<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+{
+	fn get_proof(
+		&self,
+		claim: String,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<Option<Proof<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		let proof = api.get_claim(&at, claim)
+			.map_err(|e| runtime_error("Unable to query claim.", e))?;
+
+		Ok(proof.map(|(owner, block_number)| Proof { owner, block_number }))
+	}
+
+	fn get_proofs(
+		&self,
+		claims: Vec<String>,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<Vec<Option<Proof<AccountId, BlockNumber>>>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claims = claims.iter().map(|claim| decode_claim(claim)).collect::<Result<Vec<_>>>()?;
+
+		let proofs = api.get_claims(&at, claims)
+			.map_err(|e| runtime_error("Unable to query claims.", e))?
+			.map_err(|code| RpcError {
+				code: ErrorCode::InvalidParams,
+				message: "Too many claims queried at once.".into(),
+				data: Some(code.into()),
+			})?;
+
+		Ok(proofs
+			.into_iter()
+			.map(|proof| proof.map(|(owner, block_number)| Proof { owner, block_number }))
+			.collect())
+	}
+
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		start_key: Option<String>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<ClaimsPage> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let start_key = start_key.map(|key| decode_hex(&key, "start key")).transpose()?;
+
+		let (claims, next_key) = api.claims_of_paged(&at, owner, start_key, limit)
+			.map_err(|e| runtime_error("Unable to query claims.", e))?;
+
+		Ok(ClaimsPage {
+			claims: claims.into_iter().map(Bytes).collect(),
+			next_key: next_key.map(Bytes),
+		})
+	}
+
+	fn claims_in_range(
+		&self,
+		from: BlockNumber,
+		to: BlockNumber,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<ClaimsRange<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let (claims, next_block) = api.claims_in_range(&at, from, to, limit)
+			.map_err(|e| runtime_error("Unable to query claims in the block range.", e))?;
+
+		Ok(ClaimsRange { claims: claims.into_iter().map(Bytes).collect(), next_block })
+	}
+
+	fn derive_key(&self, claim: String, at: Option<<Block as BlockT>::Hash>) -> Result<Bytes> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		api.derive_key(&at, claim).map(Into::into)
+			.map_err(|e| runtime_error("Unable to derive the key of the claim.", e))
+	}
+
+	fn is_owner(&self, claim: String, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<bool> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		api.is_owner(&at, claim, who)
+			.map_err(|e| runtime_error("Unable to query the owners of the claim.", e))
+	}
+
+	fn can_create(
+		&self,
+		claim: String,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<std::result::Result<(), u8>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		api.can_create(&at, claim, who)
+			.map_err(|e| runtime_error("Unable to check whether the claim can be created.", e))
+	}
+
+	fn claim_details(
+		&self,
+		claim: String,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<Option<ClaimDetails<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		let details = api.claim_details(&at, claim)
+			.map_err(|e| runtime_error("Unable to query the details of the claim.", e))?;
+
+		Ok(details.map(|details| ClaimDetails {
+			owner: details.owner,
+			created_at: details.created_at,
+			expiry: details.expiry,
+			metadata: details.metadata.map(Bytes),
+			frozen: details.frozen,
+			locked_until: details.locked_until,
+		}))
+	}
+
+	fn claim_header(&self, claim: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<ClaimHeader>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		let header = api.claim_header(&at, claim)
+			.map_err(|e| runtime_error("Unable to query the header of the claim.", e))?;
+
+		Ok(header.map(|header| ClaimHeader { version: header.version, kind: header.kind }))
+	}
+
+	fn claim_age(&self, claim: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let claim = decode_claim(&claim)?;
+
+		api.claim_age(&at, claim)
+			.map_err(|e| runtime_error("Unable to query the age of the claim.", e))
+	}
+
+	fn total_proofs(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.total_proofs(&at).map_err(|e| runtime_error("Unable to query the number of claims.", e))
+	}
+
+	fn peak_proofs(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.peak_proofs(&at)
+			.map_err(|e| runtime_error("Unable to query the peak number of claims.", e))
+	}
+
+	fn claim_count_of(&self, owner: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.claim_count_of(&at, owner)
+			.map_err(|e| runtime_error("Unable to query the number of claims of the account.", e))
+	}
+
+	fn ownership_distribution(&self, buckets: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u32>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.ownership_distribution(&at, buckets)
+			.map_err(|e| runtime_error("Unable to query the distribution of claims over accounts.", e))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpc_core::IoHandler;
+	use pallet_poe_runtime_api::{ClaimDetails as RuntimeClaimDetails, ClaimHeader as RuntimeClaimHeader, ClaimStatus};
+	use sp_blockchain::{BlockStatus, Info};
+	use sp_core::H256;
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper, Header};
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+	// A client whose runtime knows a single claim, `0x0102`, owned by account 1 since block 2.
+	struct TestClient;
+
+	struct TestApi;
+
+	impl ProvideRuntimeApi<Block> for TestClient {
+		type Api = TestApi;
+
+		fn runtime_api(&self) -> sp_api::ApiRef<'_, Self::Api> {
+			TestApi.into()
+		}
+	}
+
+	impl HeaderBackend<Block> for TestClient {
+		fn header(&self, _id: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+			Ok(None)
+		}
+
+		fn info(&self) -> Info<Block> {
+			Info {
+				best_hash: Default::default(),
+				best_number: 0,
+				genesis_hash: Default::default(),
+				finalized_hash: Default::default(),
+				finalized_number: 0,
+				number_leaves: 1,
+			}
+		}
+
+		fn status(&self, _id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+			Ok(BlockStatus::Unknown)
+		}
+
+		fn number(&self, _hash: H256) -> sp_blockchain::Result<Option<u64>> {
+			Ok(None)
+		}
+
+		fn hash(&self, _number: u64) -> sp_blockchain::Result<Option<H256>> {
+			Ok(None)
+		}
+	}
+
+	sp_api::mock_impl_runtime_apis! {
+		impl pallet_poe_runtime_api::PoeApi<Block, u64, u64> for TestApi {
+			fn get_claim(claim: Vec<u8>) -> Option<(u64, u64)> {
+				if claim == vec![1, 2] { Some((1, 2)) } else { None }
+			}
+
+			fn get_claims(_claims: Vec<Vec<u8>>) -> std::result::Result<Vec<Option<(u64, u64)>>, u8> {
+				Err(7)
+			}
+
+			fn claim_status(_claim: Vec<u8>, _who: u64) -> ClaimStatus<u64> {
+				unimplemented!()
+			}
+
+			fn claims_of_paged(
+				_owner: u64,
+				_start_key: Option<Vec<u8>>,
+				_limit: u32,
+			) -> (Vec<Vec<u8>>, Option<Vec<u8>>) {
+				unimplemented!()
+			}
+
+			fn claims_in_range(_from: u64, _to: u64, _limit: u32) -> (Vec<Vec<u8>>, Option<u64>) {
+				unimplemented!()
+			}
+
+			fn derive_key(_claim: Vec<u8>) -> Vec<u8> {
+				unimplemented!()
+			}
+
+			fn is_owner(_claim: Vec<u8>, _who: u64) -> bool {
+				unimplemented!()
+			}
+
+			fn can_create(_claim: Vec<u8>, _who: u64) -> std::result::Result<(), u8> {
+				unimplemented!()
+			}
+
+			fn claim_details(_claim: Vec<u8>) -> Option<RuntimeClaimDetails<u64, u64>> {
+				unimplemented!()
+			}
+
+			fn claim_header(_claim: Vec<u8>) -> Option<RuntimeClaimHeader> {
+				unimplemented!()
+			}
+
+			fn claim_age(_claim: Vec<u8>) -> Option<u64> {
+				unimplemented!()
+			}
+
+			fn total_proofs() -> u32 {
+				1
+			}
+
+			fn peak_proofs() -> u32 {
+				unimplemented!()
+			}
+
+			fn claim_count_of(_owner: u64) -> u32 {
+				unimplemented!()
+			}
+
+			fn ownership_distribution(_buckets: u32) -> Vec<u32> {
+				vec![1]
+			}
+		}
+	}
+
+	fn io_with(deny_unsafe: DenyUnsafe) -> IoHandler {
+		let mut io = IoHandler::new();
+		io.extend_with(PoeApi::<H256, u64, u64>::to_delegate(Poe::new(Arc::new(TestClient), deny_unsafe)));
+		io
+	}
+
+	fn io() -> IoHandler {
+		io_with(DenyUnsafe::Yes)
+	}
+
+	#[test]
+	fn rpc_queries_the_runtime_at_the_best_block() {
+		let request = r#"{"jsonrpc":"2.0","method":"poe_getProof","params":["0x0102"],"id":1}"#;
+		let response = r#"{"jsonrpc":"2.0","result":{"blockNumber":2,"owner":1},"id":1}"#;
+		assert_eq!(io().handle_request_sync(request), Some(response.into()));
+
+		let request = r#"{"jsonrpc":"2.0","method":"poe_getProof","params":["0x03"],"id":2}"#;
+		let response = r#"{"jsonrpc":"2.0","result":null,"id":2}"#;
+		assert_eq!(io().handle_request_sync(request), Some(response.into()));
+
+		let request = r#"{"jsonrpc":"2.0","method":"poe_totalProofs","params":[],"id":3}"#;
+		let response = r#"{"jsonrpc":"2.0","result":1,"id":3}"#;
+		assert_eq!(io().handle_request_sync(request), Some(response.into()));
+	}
+
+	#[test]
+	fn rpc_reports_malformed_claims_and_rejected_batches() {
+		let request = r#"{"jsonrpc":"2.0","method":"poe_getProof","params":["0xzz"],"id":1}"#;
+		let response = io().handle_request_sync(request).unwrap();
+		assert!(response.contains(r#""code":2,"message":"Unable to decode claim as hex.""#));
+
+		let request = r#"{"jsonrpc":"2.0","method":"poe_getProofs","params":[["0x0102"]],"id":2}"#;
+		let response = io().handle_request_sync(request).unwrap();
+		assert!(response.contains(r#""code":-32602,"message":"Too many claims queried at once.","data":7"#));
+	}
+
+	#[test]
+	fn decode_claim_works() {
+		assert_eq!(decode_claim("0x0102").unwrap(), vec![1, 2]);
+	}
+
+	#[test]
+	fn decode_claim_rejects_malformed_hex() {
+		let err = decode_claim("0xzz").unwrap_err();
+		assert_eq!(err.code, ErrorCode::ServerError(Error::DecodeError.into()));
+		assert!(decode_claim("0102").is_err());
+	}
+
+	#[test]
+	fn missing_proof_serializes_to_null() {
+		let proof: Option<Proof<u64, u32>> = None;
+		assert_eq!(serde_json::to_string(&proof).unwrap(), "null");
+	}
+
+	#[test]
+	fn proof_serializes_to_camel_case() {
+		let proof = Some(Proof { owner: 1u64, block_number: 2u32 });
+		assert_eq!(
+			serde_json::to_string(&proof).unwrap(),
+			r#"{"owner":1,"blockNumber":2}"#
+		);
+	}
+
+	#[test]
+	fn proofs_serialize_positionally() {
+		let proofs = vec![None, Some(Proof { owner: 1u64, block_number: 2u32 }), None];
+		assert_eq!(
+			serde_json::to_string(&proofs).unwrap(),
+			r#"[null,{"owner":1,"blockNumber":2},null]"#
+		);
+	}
+
+	#[test]
+	fn claim_details_serializes_to_camel_case() {
+		let details = ClaimDetails {
+			owner: 1u64,
+			created_at: 2u32,
+			expiry: None,
+			metadata: Some(Bytes(vec![3])),
+			frozen: false,
+			locked_until: Some(4u32),
+		};
+		assert_eq!(
+			serde_json::to_string(&details).unwrap(),
+			r#"{"owner":1,"createdAt":2,"expiry":null,"metadata":"0x03","frozen":false,"lockedUntil":4}"#
+		);
+	}
+
+	#[test]
+	fn claim_header_serializes_to_camel_case() {
+		let header = Some(ClaimHeader { version: 1, kind: 2 });
+		assert_eq!(serde_json::to_string(&header).unwrap(), r#"{"version":1,"kind":2}"#);
+	}
+
+	#[test]
+	fn claims_page_serializes_to_camel_case() {
+		let page = ClaimsPage { claims: vec![Bytes(vec![1, 2])], next_key: Some(Bytes(vec![3])) };
+		assert_eq!(
+			serde_json::to_string(&page).unwrap(),
+			r#"{"claims":["0x0102"],"nextKey":"0x03"}"#
+		);
+
+		let last = ClaimsPage { claims: vec![], next_key: None };
+		assert_eq!(serde_json::to_string(&last).unwrap(), r#"{"claims":[],"nextKey":null}"#);
+	}
+
+	#[test]
+	fn claims_range_serializes_to_camel_case() {
+		let range = ClaimsRange { claims: vec![Bytes(vec![1, 2])], next_block: Some(3u32) };
+		assert_eq!(
+			serde_json::to_string(&range).unwrap(),
+			r#"{"claims":["0x0102"],"nextBlock":3}"#
+		);
+	}
+
+	#[test]
+	fn ownership_distribution_is_unsafe() {
+		let request = r#"{"jsonrpc":"2.0","method":"poe_ownershipDistribution","params":[1],"id":1}"#;
+		let response = io().handle_request_sync(request).unwrap();
+		assert!(response.contains(r#""code":-32601"#));
+
+		let response = r#"{"jsonrpc":"2.0","result":[1],"id":1}"#;
+		assert_eq!(io_with(DenyUnsafe::No).handle_request_sync(request), Some(response.into()));
+	}
+}
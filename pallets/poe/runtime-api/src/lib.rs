@@ -0,0 +1,114 @@
+//! Runtime API definition for the proof of existence pallet.
+//!
+//! This API should be imported and implemented by the runtime of a node that wants to
+//! query claims without reconstructing raw storage keys.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The code generated by `decl_runtime_apis!` trips these lints.
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::{Decode, Encode};
+use sp_std::prelude::*;
+
+/// How a claim relates to the account asking about it.
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ClaimStatus<AccountId> {
+	/// The claim does not exist.
+	NotFound,
+	/// The claim is owned by the asking account.
+	OwnedByCaller,
+	/// The claim is owned by another account.
+	OwnedByOther(AccountId),
+}
+
+/// A small typed header stored with a claim, letting clients tell kinds of proofs apart.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ClaimHeader {
+	/// The version of the proof format. Zero for claims created without a header.
+	pub version: u16,
+	/// The kind of proof, as defined by the client.
+	pub kind: u8,
+}
+
+/// Everything a client needs to show a claim, gathered in a single query.
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ClaimDetails<AccountId, BlockNumber> {
+	/// The account that owns the claim.
+	pub owner: AccountId,
+	/// The block the claim was created in.
+	pub created_at: BlockNumber,
+	/// The block the claim expires at, if it was created with an expiry.
+	pub expiry: Option<BlockNumber>,
+	/// The metadata attached to the claim, if there is any.
+	pub metadata: Option<Vec<u8>>,
+	/// Whether the claim is frozen.
+	pub frozen: bool,
+	/// The block the claim is locked until, if it was ever locked.
+	pub locked_until: Option<BlockNumber>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query proofs of existence.
+	pub trait PoeApi<AccountId, BlockNumber> where
+		AccountId: codec::Codec,
+		BlockNumber: codec::Codec,
+	{
+		/// Get the owner of `claim` and the block it was created in, if it exists.
+		fn get_claim(claim: Vec<u8>) -> Option<(AccountId, BlockNumber)>;
+
+		/// Get the owner and creation block of each of `claims`, in the same order, or the index
+		/// of the pallet error if there are more than `MaxQueryBatch` of them.
+		fn get_claims(claims: Vec<Vec<u8>>) -> Result<Vec<Option<(AccountId, BlockNumber)>>, u8>;
+
+		/// Get whether `claim` does not exist, is owned by `who` or is owned by someone else.
+		fn claim_status(claim: Vec<u8>, who: AccountId) -> ClaimStatus<AccountId>;
+
+		/// Get up to `limit` encoded hashes of the claims owned by `owner`, starting at
+		/// `start_key`, and the key to start the next page at, or `None` after the last page.
+		fn claims_of_paged(
+			owner: AccountId,
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<Vec<u8>>, Option<Vec<u8>>);
+
+		/// Get the encoded hashes of the claims created from block `from` to block `to`, removed
+		/// claims included, and the block to carry on from. Stops once `limit` claims are collected
+		/// or `MAX_RANGE_SPAN` blocks are scanned.
+		fn claims_in_range(from: BlockNumber, to: BlockNumber, limit: u32) -> (Vec<Vec<u8>>, Option<BlockNumber>);
+
+		/// Get the encoded key that `claim` is stored under.
+		fn derive_key(claim: Vec<u8>) -> Vec<u8>;
+
+		/// Get whether `who` is an owner of `claim`, co-owners included.
+		fn is_owner(claim: Vec<u8>, who: AccountId) -> bool;
+
+		/// Check whether `who` could create `claim` now, or get the index of the pallet error
+		/// that creating it would fail with.
+		fn can_create(claim: Vec<u8>, who: AccountId) -> Result<(), u8>;
+
+		/// Get everything stored about `claim`, if it exists.
+		fn claim_details(claim: Vec<u8>) -> Option<ClaimDetails<AccountId, BlockNumber>>;
+
+		/// Get the header stored with `claim`, if it exists.
+		fn claim_header(claim: Vec<u8>) -> Option<ClaimHeader>;
+
+		/// Get how many blocks ago `claim` was created, if it exists.
+		fn claim_age(claim: Vec<u8>) -> Option<BlockNumber>;
+
+		/// Get the number of claims on chain.
+		fn total_proofs() -> u32;
+
+		/// Get the highest number of claims there have ever been on chain.
+		fn peak_proofs() -> u32;
+
+		/// Get the number of claims owned by `owner`.
+		fn claim_count_of(owner: AccountId) -> u32;
+
+		/// Get how many accounts own each number of claims, in at most `buckets` buckets. This
+		/// iterates over every account, so it is only meant for off-chain or archival nodes.
+		fn ownership_distribution(buckets: u32) -> Vec<u32>;
+	}
+}
@@ -0,0 +1,702 @@
+//! Benchmarking for the proof of existence pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+// The `benchmarks!` macro builds its component lists with `vec!`.
+#![allow(clippy::useless_vec)]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use sp_runtime::traits::Bounded;
+
+use crate::Module as PoeModule;
+
+const SEED: u32 = 0;
+
+// Create an account with enough funds to pay any number of claim deposits.
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+    let who: T::AccountId = account(name, index, SEED);
+    T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value());
+    who
+}
+
+// Advance to the next block, so that an account may create more claims.
+fn next_block<T: Trait>() {
+    let now = frame_system::Module::<T>::block_number();
+    frame_system::Module::<T>::set_block_number(now + 1.into());
+}
+
+// Advance the block number far enough for claims created now to change hands.
+fn age_claims<T: Trait>() {
+    let now = frame_system::Module::<T>::block_number();
+    frame_system::Module::<T>::set_block_number(now + T::MinClaimAge::get());
+}
+
+// Build `n` distinct claims of the maximum length, the worst case for a batch.
+fn batch_claims<T: Trait>(n: u32) -> Vec<Vec<u8>> {
+    (0 .. n).map(|i| {
+        let mut claim = vec![0u8; T::MaxClaimLength::get() as usize];
+        claim[..4].copy_from_slice(&i.to_le_bytes());
+        claim
+    }).collect()
+}
+
+benchmarks! {
+    _ { }
+
+    create_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+    }: _(RawOrigin::Signed(caller.clone()), claim.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::proofs(T::Hashing::hash(&claim)).owner, caller);
+    }
+
+    revoke_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(!Proofs::<T>::contains_key(T::Hashing::hash(&claim)));
+    }
+
+    transfer_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let dest = funded_account::<T>("dest", 0);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        age_claims::<T>();
+    }: _(RawOrigin::Signed(caller), dest.clone(), claim.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::proofs(T::Hashing::hash(&claim)).owner, dest);
+    }
+
+    set_claim_metadata {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let m in 0 .. T::MaxMetadataLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        let metadata = vec![1u8; m as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), metadata.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::metadata_of(T::Hashing::hash(&claim)), metadata);
+    }
+
+    propose_transfer {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let dest = funded_account::<T>("dest", 0);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), dest.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::pending_transfer(T::Hashing::hash(&claim)), Some(dest));
+    }
+
+    accept_transfer {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone())?;
+        PoeModule::<T>::propose_transfer(RawOrigin::Signed(owner).into(), claim.clone(), caller.clone())?;
+        age_claims::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), claim.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::proofs(T::Hashing::hash(&claim)).owner, caller);
+    }
+
+    cancel_transfer {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let dest = funded_account::<T>("dest", 0);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        PoeModule::<T>::propose_transfer(RawOrigin::Signed(caller.clone()).into(), claim.clone(), dest)?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::pending_transfer(T::Hashing::hash(&claim)), None);
+    }
+
+    reclaim_expired {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        // A claim with no time to live expires in the block it is created.
+        PoeModule::<T>::create_claim_with_expiry(RawOrigin::Signed(owner).into(), claim.clone(), 0.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), claim.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::proofs(T::Hashing::hash(&claim)).owner, caller);
+    }
+
+    revoke_if_expired {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let caller: T::AccountId = whitelisted_caller();
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim_with_expiry(RawOrigin::Signed(owner).into(), claim.clone(), 0.into())?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(!Proofs::<T>::contains_key(T::Hashing::hash(&claim)));
+    }
+
+    set_operator {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+    verify {
+        assert!(PoeModule::<T>::is_operator(&caller, &operator));
+    }
+
+    set_paused {
+    }: _(RawOrigin::Root, true)
+    verify {
+        assert!(PoeModule::<T>::paused());
+    }
+
+    add_to_allowlist {
+        let who: T::AccountId = account("notary", 0, SEED);
+    }: _(RawOrigin::Root, who.clone())
+    verify {
+        assert!(PoeModule::<T>::is_allowlisted(&who));
+    }
+
+    remove_from_allowlist {
+        let who: T::AccountId = account("notary", 0, SEED);
+        PoeModule::<T>::add_to_allowlist(RawOrigin::Root.into(), who.clone())?;
+    }: _(RawOrigin::Root, who.clone())
+    verify {
+        assert!(!PoeModule::<T>::is_allowlisted(&who));
+    }
+
+    revoke_expired {
+        let n in 1 .. T::MaxExpiredPerBlock::get();
+        let owner = funded_account::<T>("owner", 0);
+        let mut claims = Vec::new();
+        for i in 0 .. n {
+            let claim = i.to_le_bytes().to_vec();
+            // A claim with no time to live expires in the block it is created.
+            PoeModule::<T>::create_claim_with_expiry(RawOrigin::Signed(owner.clone()).into(), claim.clone(), 0.into())?;
+            claims.push(T::Hashing::hash(&claim));
+            next_block::<T>();
+        }
+    }: _(RawOrigin::None, claims.clone())
+    verify {
+        assert!(claims.iter().all(|claim_hash| !Proofs::<T>::contains_key(claim_hash)));
+    }
+
+    rename_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let old_claim = vec![0u8; l as usize];
+        let new_claim = vec![1u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), old_claim.clone())?;
+    }: _(RawOrigin::Signed(caller.clone()), old_claim.clone(), new_claim.clone())
+    verify {
+        assert!(!Proofs::<T>::contains_key(T::Hashing::hash(&old_claim)));
+        assert_eq!(PoeModule::<T>::proofs(T::Hashing::hash(&new_claim)).owner, caller);
+    }
+
+    force_create {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let claim = vec![0u8; l as usize];
+        let origin = T::ForceOrigin::successful_origin();
+    }: {
+        PoeModule::<T>::force_create(origin, claim.clone(), owner.clone(), 1.into())?;
+    }
+    verify {
+        assert_eq!(PoeModule::<T>::proofs(T::Hashing::hash(&claim)).owner, owner);
+    }
+
+    revoke_all {
+        let n in 1 .. T::MaxRevokeAll::get().min(T::MaxClaimsPerAccount::get());
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        for i in 0 .. n {
+            PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), i.to_le_bytes().to_vec())?;
+            next_block::<T>();
+        }
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(PoeModule::<T>::claim_count_of(&caller), 0);
+    }
+
+    create_claims {
+        let n in 1 .. T::MaxBatch::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claims = batch_claims::<T>(n);
+    }: _(RawOrigin::Signed(caller.clone()), claims, false)
+    verify {
+        assert_eq!(PoeModule::<T>::claim_count_of(&caller), n);
+    }
+
+    transfer_claims {
+        let n in 1 .. T::MaxBatch::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let dest = funded_account::<T>("dest", 0);
+        let claims = batch_claims::<T>(n);
+        PoeModule::<T>::create_claims(RawOrigin::Signed(caller.clone()).into(), claims.clone(), false)?;
+        age_claims::<T>();
+    }: _(RawOrigin::Signed(caller), claims, dest.clone(), false)
+    verify {
+        assert_eq!(PoeModule::<T>::claim_count_of(&dest), n);
+    }
+
+    lock_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        PoeModule::<T>::lock_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), 1.into())?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), 2.into())
+    verify {
+        assert_eq!(PoeModule::<T>::locked_until(T::Hashing::hash(&claim)), Some(2.into()));
+    }
+
+    extend_expiry {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim_with_expiry(RawOrigin::Signed(caller.clone()).into(), claim.clone(), 0.into())?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), 1.into())
+    verify {
+        assert_eq!(PoeModule::<T>::expiry_of(T::Hashing::hash(&claim)), Some(1.into()));
+    }
+
+    set_auto_renew {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim_with_expiry(RawOrigin::Signed(caller.clone()).into(), claim.clone(), 0.into())?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), Some(1.into()))
+    verify {
+        assert_eq!(PoeModule::<T>::auto_renew_of(T::Hashing::hash(&claim)), Some(1.into()));
+    }
+
+    touch_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim_with_expiry(RawOrigin::Signed(caller.clone()).into(), claim.clone(), 0.into())?;
+        PoeModule::<T>::set_auto_renew(RawOrigin::Signed(caller.clone()).into(), claim.clone(), Some(1.into()))?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        let expiry = frame_system::Module::<T>::block_number() + 1.into();
+        assert_eq!(PoeModule::<T>::expiry_of(T::Hashing::hash(&claim)), Some(expiry));
+    }
+
+    challenge_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(owner).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(Challenges::<T>::contains_key(T::Hashing::hash(&claim)));
+    }
+
+    resolve_challenge {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let challenger = funded_account::<T>("challenger", 0);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(owner).into(), claim.clone())?;
+        PoeModule::<T>::challenge_claim(RawOrigin::Signed(challenger).into(), claim.clone())?;
+        let origin = T::ResolveOrigin::successful_origin();
+    }: {
+        PoeModule::<T>::resolve_challenge(origin, claim.clone(), true)?;
+    }
+    verify {
+        assert!(!Proofs::<T>::contains_key(T::Hashing::hash(&claim)));
+    }
+
+    withdraw_challenge {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(owner).into(), claim.clone())?;
+        PoeModule::<T>::challenge_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        let end = frame_system::Module::<T>::block_number().saturating_add(T::ChallengePeriod::get());
+        frame_system::Module::<T>::set_block_number(end.saturating_add(1.into()));
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(!Challenges::<T>::contains_key(T::Hashing::hash(&claim)));
+    }
+
+    freeze_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(PoeModule::<T>::is_frozen(T::Hashing::hash(&claim)));
+    }
+
+    unfreeze_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        PoeModule::<T>::freeze_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(!PoeModule::<T>::is_frozen(T::Hashing::hash(&claim)));
+    }
+
+    seal_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone())
+    verify {
+        assert!(PoeModule::<T>::is_sealed(T::Hashing::hash(&claim)));
+    }
+
+    add_owner {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), owner.clone())
+    verify {
+        assert!(OwnersOf::<T>::get(T::Hashing::hash(&claim)).contains(&owner));
+    }
+
+    // Removing the owner holding the deposit is the worst case, as the claim moves to the next
+    // owner.
+    remove_owner {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let owner = funded_account::<T>("owner", 0);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        PoeModule::<T>::add_owner(RawOrigin::Signed(caller.clone()).into(), claim.clone(), owner.clone())?;
+        age_claims::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), claim.clone(), caller.clone())
+    verify {
+        assert_eq!(Proofs::<T>::get(T::Hashing::hash(&claim)).owner, owner);
+    }
+
+    escrow_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        age_claims::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), claim.clone())
+    verify {
+        assert_eq!(Escrowed::<T>::get(T::Hashing::hash(&claim)), Some(caller));
+    }
+
+    release_claim {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let owner = funded_account::<T>("owner", 0);
+        let dest = funded_account::<T>("dest", 0);
+        let claim = vec![0u8; l as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone())?;
+        age_claims::<T>();
+        PoeModule::<T>::escrow_claim(RawOrigin::Signed(owner).into(), claim.clone())?;
+        let origin = T::EscrowOrigin::successful_origin();
+    }: {
+        PoeModule::<T>::release_claim(origin, claim.clone(), dest.clone())?;
+    }
+    verify {
+        assert_eq!(Proofs::<T>::get(T::Hashing::hash(&claim)).owner, dest);
+    }
+
+    // Replacing an earlier alias is the worst case, as it is removed from `AliasToClaim`.
+    set_alias {
+        let l in (T::MinClaimLength::get()) .. T::MaxClaimLength::get();
+        let a in 1 .. T::MaxAliasLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let claim = vec![0u8; l as usize];
+        let alias = vec![1u8; a as usize];
+        PoeModule::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+        PoeModule::<T>::set_alias(RawOrigin::Signed(caller.clone()).into(), claim.clone(), vec![2u8])?;
+    }: _(RawOrigin::Signed(caller), claim.clone(), alias.clone())
+    verify {
+        assert_eq!(PoeModule::<T>::resolve_alias(&alias), Some(T::Hashing::hash(&claim)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn create_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_create_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn revoke_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_revoke_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn transfer_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_transfer_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_claim_metadata() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_claim_metadata::<Test>());
+        });
+    }
+
+    #[test]
+    fn propose_transfer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_propose_transfer::<Test>());
+        });
+    }
+
+    #[test]
+    fn accept_transfer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_accept_transfer::<Test>());
+        });
+    }
+
+    #[test]
+    fn cancel_transfer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_cancel_transfer::<Test>());
+        });
+    }
+
+    #[test]
+    fn reclaim_expired() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_reclaim_expired::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_operator() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_operator::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_paused() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_paused::<Test>());
+        });
+    }
+
+    #[test]
+    fn revoke_expired() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_revoke_expired::<Test>());
+        });
+    }
+
+    #[test]
+    fn rename_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_rename_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn force_create() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_force_create::<Test>());
+        });
+    }
+
+    #[test]
+    fn revoke_all() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_revoke_all::<Test>());
+        });
+    }
+
+    #[test]
+    fn lock_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_lock_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn extend_expiry() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_extend_expiry::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_auto_renew() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_auto_renew::<Test>());
+        });
+    }
+
+    #[test]
+    fn touch_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_touch_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn challenge_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_challenge_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn resolve_challenge() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_resolve_challenge::<Test>());
+        });
+    }
+
+    #[test]
+    fn withdraw_challenge() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_withdraw_challenge::<Test>());
+        });
+    }
+
+    #[test]
+    fn freeze_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_freeze_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn unfreeze_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_unfreeze_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn seal_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_seal_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn add_owner() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_add_owner::<Test>());
+        });
+    }
+
+    #[test]
+    fn remove_owner() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_remove_owner::<Test>());
+        });
+    }
+
+    #[test]
+    fn add_to_allowlist() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_add_to_allowlist::<Test>());
+        });
+    }
+
+    #[test]
+    fn remove_from_allowlist() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_remove_from_allowlist::<Test>());
+        });
+    }
+
+    #[test]
+    fn escrow_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_escrow_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn release_claim() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_release_claim::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_alias() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_alias::<Test>());
+        });
+    }
+
+    #[test]
+    fn revoke_if_expired() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_revoke_if_expired::<Test>());
+        });
+    }
+
+    #[test]
+    fn create_claims() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_create_claims::<Test>());
+        });
+    }
+
+    #[test]
+    fn transfer_claims() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_transfer_claims::<Test>());
+        });
+    }
+}
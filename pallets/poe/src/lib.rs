@@ -1,12 +1,38 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#![recursion_limit = "256"]
 
 /// Edit this file to define custom logic or remove it if it is not needed.
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure, dispatch};
-use frame_system::ensure_signed;
-use sp_std::prelude::*;
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, storage, transactional, Blake2_128Concat,
+    Parameter, StorageHasher, sp_io,
+    dispatch::IsSubType,
+    traits::{BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement, Get, Imbalance, ReservableCurrency},
+    weights::{DispatchClass, Pays, Weight},
+    IterableStorageDoubleMap, IterableStorageMap,
+};
+use frame_system::{ensure_none, ensure_root, ensure_signed, offchain::{SendTransactionTypes, SubmitTransaction}, RawOrigin};
+use sp_runtime::{
+    traits::{
+        AccountIdConversion, CheckedAdd, DispatchInfoOf, Hash, IdentifyAccount, One, Saturating, SignedExtension,
+        TrailingZeroInput, Verify, Zero,
+    },
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+        TransactionValidityError, ValidTransaction,
+    },
+    ModuleId, Perbill, RuntimeDebug,
+};
+use sp_std::{collections::btree_set::BTreeSet, fmt, marker::PhantomData, prelude::*};
+
+pub use pallet_poe_runtime_api::{ClaimDetails, ClaimHeader, ClaimStatus};
+
+pub mod migrations;
+mod benchmarking;
+mod weights;
 
 #[cfg(test)]
 mod mock;
@@ -14,35 +40,616 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Weight functions needed for this pallet.
+pub trait WeightInfo {
+    fn create_claim(l: u32) -> Weight;
+    fn revoke_claim(l: u32) -> Weight;
+    fn transfer_claim(l: u32) -> Weight;
+    fn set_claim_metadata(l: u32, m: u32) -> Weight;
+    fn propose_transfer(l: u32) -> Weight;
+    fn accept_transfer(l: u32) -> Weight;
+    fn cancel_transfer(l: u32) -> Weight;
+    fn reclaim_expired(l: u32) -> Weight;
+    fn set_operator() -> Weight;
+    fn set_paused() -> Weight;
+    fn add_to_allowlist() -> Weight;
+    fn remove_from_allowlist() -> Weight;
+    fn force_create(l: u32) -> Weight;
+    fn revoke_expired(n: u32) -> Weight;
+    fn rename_claim(l: u32) -> Weight;
+    fn revoke_all(n: u32) -> Weight;
+    fn lock_claim(l: u32) -> Weight;
+    fn extend_expiry(l: u32) -> Weight;
+    fn set_auto_renew(l: u32) -> Weight;
+    fn touch_claim(l: u32) -> Weight;
+    fn challenge_claim(l: u32) -> Weight;
+    fn resolve_challenge(l: u32) -> Weight;
+    fn withdraw_challenge(l: u32) -> Weight;
+    fn freeze_claim(l: u32) -> Weight;
+    fn unfreeze_claim(l: u32) -> Weight;
+    fn add_owner(l: u32) -> Weight;
+    fn remove_owner(l: u32) -> Weight;
+    fn escrow_claim(l: u32) -> Weight;
+    fn release_claim(l: u32) -> Weight;
+    fn set_alias(l: u32, a: u32) -> Weight;
+    fn revoke_if_expired(l: u32) -> Weight;
+    fn create_claims(n: u32) -> Weight;
+    fn transfer_claims(n: u32) -> Weight;
+    fn seal_claim(l: u32) -> Weight;
+}
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
-pub trait Trait: frame_system::Trait {
+pub trait Trait: frame_system::Trait + pallet_timestamp::Trait + SendTransactionTypes<Call<Self>> {
     /// Because this pallet emits events, it depends on the runtime's definition of an event.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The minimum length in bytes of a claim. Set to at least 1 to reject empty claims.
+    type MinClaimLength: Get<u32>;
+
+    /// The maximum length in bytes of a claim.
+    type MaxClaimLength: Get<u32>;
+
+    /// The maximum number of claims a single account may own.
+    type MaxClaimsPerAccount: Get<u32>;
+
+    /// The maximum number of claims a single account may create in one block.
+    type MaxClaimsPerBlock: Get<u32>;
+
+    /// The maximum number of claims `CreatedInBlock` records for each block. No more claims can be
+    /// created in a block once it is full.
+    type MaxCreatedPerBlock: Get<u32>;
+
+    /// Whether events carry only the hash of a claim. If not set, creating a claim from raw bytes
+    /// also emits `ClaimBytes` with the bytes, which suits chains whose claims are small.
+    type EmitHashedEventsOnly: Get<bool>;
+
+    /// Whether claims are normalized before they are hashed, by lowercasing ASCII letters and
+    /// trimming trailing zero bytes, so that for instance `Foo` and `foo` name the same claim.
+    type NormalizeKeys: Get<bool>;
+
+    /// Whether only accounts in `Allowlist` may create claims.
+    type RequireAllowlist: Get<bool>;
+
+    /// The currency in which claim deposits are reserved.
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// The amount reserved from the owner of each claim.
+    type ClaimDeposit: Get<BalanceOf<Self>>;
+
+    /// The part of the non-refundable fee paid to the treasury for each claim created that does
+    /// not depend on how full the store is.
+    type FeeBase: Get<BalanceOf<Self>>;
+
+    /// How much the fee for creating a claim grows with every claim already on chain, so that
+    /// creating claims gets dearer as the store fills up.
+    type FeeSlope: Get<BalanceOf<Self>>;
+
+    /// The part of the deposit of an expired claim paid to whoever removes it with
+    /// `revoke_if_expired`. The rest is returned to the owner.
+    type CleanupReward: Get<BalanceOf<Self>>;
+
+    /// The number of blocks after its creation within which revoking a claim refunds its whole
+    /// deposit.
+    type GracePeriod: Get<Self::BlockNumber>;
+
+    /// The part of the deposit paid to the treasury when a claim is revoked after `GracePeriod`.
+    type LateRevokePenalty: Get<Perbill>;
+
+    /// The module id of the treasury, whose account receives claim fees.
+    type TreasuryModuleId: Get<ModuleId>;
+
+    /// The maximum number of expired claims pruned in a single block.
+    type MaxExpiredPerBlock: Get<u32>;
+
+    /// The maximum number of claims migrated to a new layout in a single block.
+    type MaxMigrationsPerBlock: Get<u32>;
+
+    /// The maximum length in bytes of the metadata attached to a claim.
+    type MaxMetadataLength: Get<u32>;
+
+    /// The highest `ClaimHeader` version accepted for new claims.
+    type MaxClaimVersion: Get<u16>;
+
+    /// The maximum length in bytes of the URI of an anchored document.
+    type MaxUriLength: Get<u32>;
+
+    /// The maximum length in bytes of the reason given for revoking a claim.
+    type MaxReasonLength: Get<u32>;
+
+    /// The maximum length in bytes of the alias of a claim.
+    type MaxAliasLength: Get<u32>;
+
+    /// The maximum number of claims on chain across all accounts.
+    type MaxTotalProofs: Get<u32>;
+
+    /// How close `TotalProofs` may get to `MaxTotalProofs` before `ProofStoreNearCapacity` is
+    /// emitted.
+    type NearCapacityThreshold: Get<u32>;
+
+    /// The maximum number of transfers kept in the history of a claim.
+    type MaxHistoryLength: Get<u32>;
+
+    /// The amount reserved from an account challenging a claim.
+    type ChallengeBond: Get<BalanceOf<Self>>;
+
+    /// The number of blocks a challenge stays open for resolution.
+    type ChallengePeriod: Get<Self::BlockNumber>;
+
+    /// The origin allowed to force-create and force-revoke claims.
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The origin allowed to resolve challenges.
+    type ResolveOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The module id of the escrow, whose account holds claims placed in escrow.
+    type EscrowModuleId: Get<ModuleId>;
+
+    /// The origin allowed to release claims from escrow.
+    type EscrowOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The maximum number of blocks from now at which a claim may be set to expire.
+    type MaxExpiry: Get<Self::BlockNumber>;
+
+    /// Whether sealed claims may no longer be revoked either.
+    type SealBlocksRevoke: Get<bool>;
+
+    /// The maximum number of claims removed by a single `revoke_all` call.
+    type MaxRevokeAll: Get<u32>;
+
+    /// The maximum number of claims in a single `create_claims` or `transfer_claims` call.
+    type MaxBatch: Get<u32>;
+
+    /// The maximum number of claims looked up by a single `get_claims` query.
+    type MaxQueryBatch: Get<u32>;
+
+    /// The maximum number of owners a claim may have.
+    type MaxOwners: Get<u32>;
+
+    /// The number of blocks after its creation before a claim can change hands.
+    type MinClaimAge: Get<Self::BlockNumber>;
+
+    /// The priority of the unsigned transactions submitted by the off-chain worker.
+    type UnsignedPriority: Get<TransactionPriority>;
+
+    /// The signature with which owners authorize claims relayed through `create_claim_signed`.
+    type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+    /// The public key that verifies an `OffchainSignature`, identifying the account it belongs to.
+    type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+    /// Weight information for the extrinsics of this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+/// The prefix of the messages owners sign to authorize `create_claim_signed`.
+pub const SIGNED_CLAIM_PREFIX: &[u8] = b"<Bytes>poe:create_claim_signed</Bytes>";
+
+/// The prefix of the messages cosigners sign to approve `create_claim_cosigned`.
+pub const COSIGNED_CLAIM_PREFIX: &[u8] = b"<Bytes>poe:create_claim_cosigned</Bytes>";
+
+/// The most buckets `ownership_distribution` splits accounts into.
+pub const MAX_OWNERSHIP_BUCKETS: u32 = 64;
+
+/// The most blocks a single `claims_in_range` query scans.
+pub const MAX_RANGE_SPAN: u32 = 1_000;
+
+/// The most `ExpiringAt` blocks and entries looked at by the pruning of a single block.
+pub const MAX_EXPIRY_SCAN: u32 = 64;
+
+/// The key a claim is stored under: the hash of the claimed bytes.
+pub type ClaimHash<T> = <T as frame_system::Trait>::Hash;
+
+/// A numeric id assigned to each claim when it is created. Ids are never reused.
+pub type ClaimId = u64;
+
+/// The balance type of the configured currency.
+pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// The owner of a claim and the block it was created in.
+pub type ProofOf<T> = (<T as frame_system::Trait>::AccountId, <T as frame_system::Trait>::BlockNumber);
+
+/// Everything stored about a claim.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct ClaimInfo<AccountId, BlockNumber, Balance, Moment> {
+    /// The account that owns the claim.
+    pub owner: AccountId,
+    /// The account that created the claim. Unlike `owner`, this never changes.
+    pub creator: AccountId,
+    /// The block the claim was created in.
+    pub created_at: BlockNumber,
+    /// The timestamp of the block the claim was created in.
+    pub timestamp: Moment,
+    /// The deposit reserved from the owner for the claim.
+    pub deposit: Balance,
+}
+
+/// The `ClaimInfo` stored for a runtime.
+pub type ClaimInfoOf<T> = ClaimInfo<
+    <T as frame_system::Trait>::AccountId,
+    <T as frame_system::Trait>::BlockNumber,
+    BalanceOf<T>,
+    <T as pallet_timestamp::Trait>::Moment,
+>;
+
+/// A document kept off-chain, anchored by the hash of its content.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct AnchorInfo<AccountId, BlockNumber, Hash> {
+    /// The account that owns the anchor.
+    pub owner: AccountId,
+    /// The block the anchor was created in.
+    pub created_at: BlockNumber,
+    /// The hash of the document, which is also the key of the claim.
+    pub content_hash: Hash,
+    /// Where the document can be fetched from.
+    pub uri: Vec<u8>,
+}
+
+// The storage layout version, used by `on_runtime_upgrade` to decide which migrations to run.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+    /// Claims are stored as an `(owner, created_at, deposit)` tuple.
+    V1,
+    /// Claims are stored as a `ClaimInfo` without a `creator`.
+    V2,
+    /// Claims are stored as a `ClaimInfo` with a `creator`.
+    V3,
+    /// Claims are stored as a `ClaimInfo` with a `creator` and a `timestamp`.
+    V4,
+    /// The number of claims is tracked in `TotalProofs`.
+    V5,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1
+    }
 }
 
 // The pallet's runtime storage items.
 // https://substrate.dev/docs/en/knowledgebase/runtime/storage
-decl_storage! {
-    // A unique name is used to ensure that the pallet's storage items are isolated.
-    // This name may be updated, but each pallet in the runtime must use a unique name.
-    // ---------------------------------vvvvvvvvvvvvvv
-    trait Store for Module<T: Trait> as TemplateModule {
-        /// The storage item for our proofs.
-        /// It maps a proof to the user who made the claim and when they made it.
-        Proofs get(fn proofs): map hasher(blake2_128_concat) Vec<u8> => (T::AccountId, T::BlockNumber);
-    }
+//
+// `decl_storage!` only accepts a literal hasher, so the storage is declared through this macro
+// with the hasher for `Proofs` picked by the `proofs-twox-hasher` feature below.
+macro_rules! decl_poe_storage {
+    ($proofs_hasher:ident) => {
+        decl_storage! {
+            // A unique name is used to ensure that the pallet's storage items are isolated.
+            // This name may be updated, but each pallet in the runtime must use a unique name.
+            // ---------------------------------vvvvvvvvvvvvvv
+            trait Store for Module<T: Trait> as TemplateModule {
+                /// The storage item for our proofs.
+                /// It maps the hash of a proof to the user who made the claim, when they made it and the
+                /// deposit reserved for it.
+                ///
+                /// Earlier versions of this pallet keyed this map by the raw claim bytes. Chains upgrading
+                /// from such a version must re-key every entry under `Module::derive_key(&claim)`; the old
+                /// `blake2_128_concat` keys still contain the raw claim, so this can be done in place.
+                //
+                // Claims are only ever stored by hash, and every byte vector below is checked against
+                // its `Max*Length` bound before it is written, so entry sizes stay bounded without a
+                // `BoundedVec` (which this version of FRAME does not have).
+                Proofs get(fn proofs): map hasher($proofs_hasher) ClaimHash<T> => ClaimInfoOf<T>;
+
+                /// The number of claims owned by each account.
+                ClaimsOf get(fn claim_count_of): map hasher(blake2_128_concat) T::AccountId => u32;
+
+                /// Index of the claims owned by each account.
+                ClaimsByOwner: double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) ClaimHash<T> => ();
+
+                /// The block at which a claim expires, for claims created with an expiry.
+                ExpiryOf get(fn expiry_of): map hasher(blake2_128_concat) ClaimHash<T> => Option<T::BlockNumber>;
+
+                /// The claims due to expire at each block, for pruning. Entries are not removed when
+                /// an expiry changes, so the pruning skips those that no longer match `ExpiryOf`.
+                ExpiringAt get(fn expiring_at): map hasher(twox_64_concat) T::BlockNumber => Vec<ClaimHash<T>>;
+
+                /// The first block whose entries in `ExpiringAt` may not all have been pruned yet.
+                PruneCursor get(fn prune_cursor): T::BlockNumber;
+
+                /// The time to live a claim's expiry is reset to whenever its owner touches it, for
+                /// claims with auto-renew switched on.
+                AutoRenewOf get(fn auto_renew_of): map hasher(blake2_128_concat) ClaimHash<T> => Option<T::BlockNumber>;
+
+                /// A short description or URI attached to a claim by its owner, at most
+                /// `MaxMetadataLength` bytes.
+                ///
+                /// This is kept out of `ClaimInfo`, so claims of every storage version read as having
+                /// empty metadata and no migration is needed for it.
+                MetadataOf get(fn metadata_of): map hasher(blake2_128_concat) ClaimHash<T> => Vec<u8>;
+
+                /// The typed header of a claim created with `create_claim_with_header`. Like
+                /// `MetadataOf` it is kept out of `ClaimInfo`, so other claims read as having the
+                /// default header of version zero.
+                HeaderOf get(fn header_of): map hasher(blake2_128_concat) ClaimHash<T> => ClaimHeader;
+
+                /// The URI of the document anchored by a claim created with `create_anchored`, at most
+                /// `MaxUriLength` bytes.
+                UriOf get(fn uri_of): map hasher(blake2_128_concat) ClaimHash<T> => Vec<u8>;
+
+                /// The block a claim last changed hands in. `created_at` in `ClaimInfo` keeps the
+                /// block it was created in.
+                LastTransfer get(fn last_transfer): map hasher(blake2_128_concat) ClaimHash<T> => Option<T::BlockNumber>;
+
+                /// The id the next claim will be assigned.
+                NextClaimId get(fn next_claim_id): ClaimId;
+
+                /// The hash of the claim with a given id.
+                ClaimById get(fn claim_by_id): map hasher(twox_64_concat) ClaimId => Option<ClaimHash<T>>;
+
+                /// The id of a claim.
+                IdOf get(fn id_of): map hasher(blake2_128_concat) ClaimHash<T> => Option<ClaimId>;
+
+                /// Who revoked a claim with `revoke_claim_with_reason`, when and why. Entries are kept
+                /// after the claim is removed, and replaced if it is created and revoked again. The
+                /// reason is at most `MaxReasonLength` bytes.
+                RevocationLog get(fn revocation_log): map hasher(blake2_128_concat) ClaimHash<T> => Option<(T::AccountId, T::BlockNumber, Vec<u8>)>;
+
+                /// The nonce an account last created a claim with through `create_claim_idempotent`,
+                /// and the claim it created.
+                LastNonceOf get(fn last_nonce_of): map hasher(blake2_128_concat) T::AccountId => Option<(u64, ClaimHash<T>)>;
+
+                /// The nonce the next claim an account authorizes for `create_claim_signed` or
+                /// cosigns for `create_claim_cosigned` must be signed with. Raised with every such
+                /// claim, so that no signature is used twice.
+                SignedClaimNonce get(fn signed_claim_nonce): map hasher(blake2_128_concat) T::AccountId => u64;
+
+                /// The block an account last created claims in and how many it created in it.
+                ClaimsThisBlock: map hasher(blake2_128_concat) T::AccountId => (T::BlockNumber, u32);
+
+                /// The account a claim's owner has proposed to transfer it to, until they accept it.
+                PendingTransfer get(fn pending_transfer): map hasher(blake2_128_concat) ClaimHash<T> => Option<T::AccountId>;
+
+                /// The most recent owners a claim was transferred to and when, oldest first.
+                HistoryOf: map hasher(blake2_128_concat) ClaimHash<T> => Vec<(T::AccountId, T::BlockNumber)>;
+
+                /// Whether an owner has approved an operator to revoke and transfer claims on their behalf.
+                Operators get(fn is_operator): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+
+                /// The number of claims on chain.
+                TotalProofs get(fn total_proofs): u32;
+
+                /// The sequence number of the next event of this pallet. Every event is indexed under
+                /// its sequence number, so the events of blocks that were reorganized away reuse it.
+                /// Every event about a claim also carries it as its last field.
+                ClaimSeq get(fn claim_seq): u64;
+
+                /// The highest `TotalProofs` has ever been. It never decreases.
+                PeakProofs get(fn peak_proofs): u32;
+
+                /// The number of claims created, removed and transferred in the current block. Reset
+                /// at the start of every block and reported by `BlockClaimActivity` at its end.
+                BlockActivity get(fn block_activity): (u32, u32, u32);
+
+                /// The accounts Root approved to create claims when `RequireAllowlist` is set.
+                Allowlist get(fn is_allowlisted): map hasher(blake2_128_concat) T::AccountId => bool;
+
+                /// Whether `ProofStoreNearCapacity` has been emitted since the store was last below the
+                /// threshold.
+                NearCapacity get(fn near_capacity): bool;
+
+                /// Whether state-changing extrinsics are currently rejected.
+                Paused get(fn paused): bool;
+
+                /// The category of a claim, for claims created with one.
+                CategoryOf get(fn category_of): map hasher(blake2_128_concat) ClaimHash<T> => Option<u8>;
+
+                /// The claims in each category.
+                ClaimsByCategory: double_map hasher(twox_64_concat) u8, hasher(blake2_128_concat) ClaimHash<T> => ();
+
+                /// The open challenge against a claim: the challenger, the last block it may be resolved in
+                /// and the bond reserved from the challenger.
+                Challenges get(fn challenge_of): map hasher(blake2_128_concat) ClaimHash<T> => Option<(T::AccountId, T::BlockNumber, BalanceOf<T>)>;
+
+                /// The account holding the deposit of a claim created on behalf of its owner. The deposit
+                /// of any other claim is held by its owner.
+                DepositorOf: map hasher(blake2_128_concat) ClaimHash<T> => Option<T::AccountId>;
+
+                /// Whether a claim is frozen, so that it may be neither revoked nor transferred.
+                Frozen get(fn is_frozen): map hasher(blake2_128_concat) ClaimHash<T> => bool;
+
+                /// Whether a claim is sealed to its owner for good, so that it may be neither
+                /// transferred nor renamed, nor revoked if `SealBlocksRevoke` is set.
+                Sealed get(fn is_sealed): map hasher(blake2_128_concat) ClaimHash<T> => bool;
+
+                /// The block until which a claim may be neither revoked nor transferred.
+                LockedUntil get(fn locked_until): map hasher(blake2_128_concat) ClaimHash<T> => Option<T::BlockNumber>;
+
+                /// The accounts sharing ownership of a claim, starting with the owner in its
+                /// `ClaimInfo`, who holds the deposit. Any of them may revoke the claim. Claims
+                /// created before co-ownership have no entry and are owned by their owner alone.
+                OwnersOf: map hasher(blake2_128_concat) ClaimHash<T> => Vec<T::AccountId>;
+
+                /// The owner who placed a claim in escrow. The claim is owned by the escrow account
+                /// until it is released, while its deposit stays reserved from the account that held it.
+                Escrowed get(fn escrowed_by): map hasher(blake2_128_concat) ClaimHash<T> => Option<T::AccountId>;
+
+                /// The claim each alias resolves to. Aliases are unique across all claims.
+                AliasToClaim: map hasher(blake2_128_concat) Vec<u8> => Option<ClaimHash<T>>;
+
+                /// The alias its owner gave a claim, if any.
+                AliasOf get(fn alias_of): map hasher(blake2_128_concat) ClaimHash<T> => Option<Vec<u8>>;
+
+                /// The claims created in each block, for block explorers. At most `MaxCreatedPerBlock`
+                /// are recorded per block, and entries are kept after the claims are removed.
+                CreatedInBlock get(fn claims_created_in_block): map hasher(twox_64_concat) T::BlockNumber => Vec<ClaimHash<T>>;
+
+                /// The storage layout version of this pallet.
+                StorageVersion build(|_: &GenesisConfig<T>| Releases::V5): Releases;
+
+                /// The last claim migrated by a `Proofs` migration too large for a single block, which
+                /// carries on after it in the next block. Claims not migrated yet read as missing until
+                /// the migration has finished.
+                MigrationCursor get(fn migration_cursor): Option<ClaimHash<T>>;
+            }
+            add_extra_genesis {
+                /// Claims to create at genesis, with their owners. Seeded claims hold no deposit.
+                config(initial_claims): Vec<(Vec<u8>, T::AccountId)>;
+                build(|config: &GenesisConfig<T>| {
+                    let now = frame_system::Module::<T>::block_number();
+                    let timestamp = pallet_timestamp::Module::<T>::get();
+                    for (claim, owner) in config.initial_claims.iter() {
+                        let claim_hash = <Module<T>>::derive_key(claim);
+                        assert!(!Proofs::<T>::contains_key(&claim_hash), "duplicate claim in genesis");
+                        assert!(TotalProofs::get() < T::MaxTotalProofs::get(), "too many claims in genesis");
+                        assert!(<Module<T>>::block_has_room(now), "too many claims in genesis block");
+                        <Module<T>>::insert_claim(&claim_hash, ClaimInfo {
+                            owner: owner.clone(),
+                            creator: owner.clone(),
+                            created_at: now,
+                            timestamp,
+                            deposit: Zero::zero(),
+                        });
+                    }
+                });
+            }
+        }
+    };
 }
 
+// `blake2_128_concat` keeps `Proofs` safe against keys crafted to collide, which matters because
+// anyone can pick the claim being hashed. `twox_64_concat` is cheaper to compute but offers no
+// such protection, so only enable `proofs-twox-hasher` where every claim comes from a trusted
+// party. The hasher decides where claims live in the trie: it must be chosen at genesis, and
+// switching it on a live chain requires re-keying every claim.
+#[cfg(not(feature = "proofs-twox-hasher"))]
+decl_poe_storage!(blake2_128_concat);
+#[cfg(feature = "proofs-twox-hasher")]
+decl_poe_storage!(twox_64_concat);
+
+/// The hasher used for the keys of `Proofs`.
+#[cfg(not(feature = "proofs-twox-hasher"))]
+pub type ProofsHasher = frame_support::Blake2_128Concat;
+/// The hasher used for the keys of `Proofs`.
+#[cfg(feature = "proofs-twox-hasher")]
+pub type ProofsHasher = frame_support::Twox64Concat;
+
 // Pallets use events to inform users when important changes are made.
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
-    pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
-        /// Event emitted when a proof has been claimed. [who, claim]
-        ClaimCreated(AccountId, Vec<u8>),
-        /// Event emitted when a claim is revoked by the owner. [who, claim]
-        ClaimRevoked(AccountId, Vec<u8>),
-		/// Event emitted when a claim's owner is changed.
-        ClaimTransfered(AccountId, AccountId, Vec<u8>),
+    pub enum Event<T> where
+        AccountId = <T as frame_system::Trait>::AccountId,
+        ClaimHash = ClaimHash<T>,
+        BlockNumber = <T as frame_system::Trait>::BlockNumber,
+        Moment = <T as pallet_timestamp::Trait>::Moment,
+        Balance = BalanceOf<T>,
+    {
+        /// Event emitted when a proof has been claimed. [who, claim_hash, claim_len, block_number, timestamp, id, seq]
+        ClaimCreated(AccountId, ClaimHash, u32, BlockNumber, Moment, ClaimId, u64),
+        /// Event emitted when a claim is revoked by the owner, along with the part of the deposit
+        /// refunded and the part paid to the treasury as a late revocation penalty.
+        /// [who, claim_hash, block_number, refunded, penalty, seq]
+        ClaimRevoked(AccountId, ClaimHash, BlockNumber, Balance, Balance, u64),
+        /// Event emitted when a claim is revoked with a reason. [who, claim_hash, reason, seq]
+        ClaimRevokedWithReason(AccountId, ClaimHash, Vec<u8>, u64),
+        /// Event emitted when a claim's owner is changed, along with the block it last changed
+        /// hands in, or was created in if it never did, and the current block.
+        /// [from, to, claim_hash, creator, previous_block, block_number, seq]
+        ClaimTransfered(AccountId, AccountId, ClaimHash, AccountId, BlockNumber, BlockNumber, u64),
+        /// Event emitted when a claim has expired and been pruned. [owner, claim_hash, seq]
+        ClaimExpired(AccountId, ClaimHash, u64),
+        /// Event emitted when a claim is revoked by `ForceOrigin`. [claim_hash, seq]
+        ClaimForceRevoked(ClaimHash, u64),
+        /// Event emitted when a batch of claims has been created. [count]
+        BatchClaimsCreated(u32),
+        /// Event emitted when a batch of claims has been transferred. [to, count]
+        BatchClaimsTransfered(AccountId, u32),
+        /// Event emitted when the metadata of a claim is set by its owner. [who, claim_hash, seq]
+        ClaimMetadataSet(AccountId, ClaimHash, u64),
+        /// Event emitted after a `transfer_claim_v2`, telling whether the metadata of the claim was
+        /// cleared on handover. [claim_hash, cleared, seq]
+        TransferMetadataHandled(ClaimHash, bool, u64),
+        /// Event emitted when a claim is created with a typed header. [claim_hash, header, seq]
+        ClaimHeaderSet(ClaimHash, ClaimHeader, u64),
+        /// Event emitted when a document is anchored by its content hash. [who, content_hash, seq]
+        ClaimAnchored(AccountId, ClaimHash, u64),
+        /// Event emitted when an owner proposes to transfer a claim. [from, to, claim_hash, seq]
+        TransferProposed(AccountId, AccountId, ClaimHash, u64),
+        /// Event emitted when an owner withdraws a proposed transfer. [who, claim_hash, seq]
+        TransferCancelled(AccountId, ClaimHash, u64),
+        /// Event emitted when an expired claim is taken over by a new owner. [new_owner, claim_hash, seq]
+        ClaimReclaimed(AccountId, ClaimHash, u64),
+        /// Event emitted when an owner approves or disapproves an operator. [owner, operator, approved]
+        OperatorSet(AccountId, AccountId, bool),
+        /// Event emitted when Root pauses or unpauses the pallet. [paused]
+        PauseSet(bool),
+        /// Event emitted at the end of a block with any claim activity in it.
+        /// [created, removed, transferred]
+        BlockClaimActivity(u32, u32, u32),
+        /// Event emitted when Root adds an account to the allowlist. [who]
+        AddedToAllowlist(AccountId),
+        /// Event emitted when Root removes an account from the allowlist. [who]
+        RemovedFromAllowlist(AccountId),
+        /// Event emitted when a claim is created by `ForceOrigin` on behalf of an owner. [owner, claim_hash, seq]
+        ClaimForceCreated(AccountId, ClaimHash, u64),
+        /// Event emitted when `ForceOrigin` imports a batch of claims. [count]
+        ClaimsForceImported(u32),
+        /// Event emitted when a claim is moved to a new key by its owner. [who, old_claim_hash, new_claim_hash, seq]
+        ClaimRenamed(AccountId, ClaimHash, ClaimHash, u64),
+        /// Event emitted when the owner replaces a mistyped claim with the correct one.
+        /// [who, old_claim_hash, new_claim_hash, seq]
+        ClaimReplaced(AccountId, ClaimHash, ClaimHash, u64),
+        /// Event emitted when an owner revokes their claims in one call. [who, count]
+        AllClaimsRevoked(AccountId, u32),
+        /// Event emitted when the owner locks a claim. [who, claim_hash, until, seq]
+        ClaimLockSet(AccountId, ClaimHash, BlockNumber, u64),
+        /// Event emitted when the owner extends the expiry of a claim. [claim_hash, new_expiry, seq]
+        ClaimExpiryExtended(ClaimHash, BlockNumber, u64),
+        /// Event emitted when the owner switches auto-renew of a claim on or off. [claim_hash, ttl, seq]
+        ClaimAutoRenewSet(ClaimHash, Option<BlockNumber>, u64),
+        /// Event emitted when the number of claims comes within `NearCapacityThreshold` of the
+        /// maximum. [current, max]
+        ProofStoreNearCapacity(u32, u32),
+        /// Event emitted when a claim is created in a category. [claim_hash, category, seq]
+        ClaimCategorized(ClaimHash, u8, u64),
+        /// Event emitted when a claim is challenged. [challenger, claim_hash, end_block, seq]
+        ClaimChallenged(AccountId, ClaimHash, BlockNumber, u64),
+        /// Event emitted when a challenge is resolved. If upheld the claim was revoked. [claim_hash, upheld, seq]
+        ChallengeResolved(ClaimHash, bool, u64),
+        /// Event emitted when an account is paid for removing an expired claim. [who, claim_hash, reward, seq]
+        CleanupRewarded(AccountId, ClaimHash, Balance, u64),
+        /// Event emitted when the deposit of a claim is slashed to the treasury because a challenge
+        /// against it was upheld. [owner, amount, seq]
+        ClaimSlashed(AccountId, Balance, u64),
+        /// Event emitted when a challenger withdraws an unresolved challenge. [challenger, claim_hash, seq]
+        ChallengeWithdrawn(AccountId, ClaimHash, u64),
+        /// Event emitted when a claim is created on behalf of another account. [signer, owner, claim_hash, seq]
+        ClaimCreatedFor(AccountId, AccountId, ClaimHash, u64),
+        /// Event emitted when a claim is created with the approval of a cosigner, who becomes its
+        /// co-owner. [who, cosigner, claim_hash, seq]
+        ClaimCosigned(AccountId, AccountId, ClaimHash, u64),
+        /// Event emitted when the owner seals a claim. [claim_hash, seq]
+        ClaimSealed(ClaimHash, u64),
+        /// Event emitted when a claim is frozen by its owner or Root. [claim_hash, seq]
+        ClaimFrozen(ClaimHash, u64),
+        /// Event emitted when a claim is unfrozen by its owner or Root. [claim_hash, seq]
+        ClaimUnfrozen(ClaimHash, u64),
+        /// Event emitted when an owner is added to a claim. [claim_hash, owner, seq]
+        OwnerAdded(ClaimHash, AccountId, u64),
+        /// Event emitted when an owner is removed from a claim. [claim_hash, owner, seq]
+        OwnerRemoved(ClaimHash, AccountId, u64),
+        /// Event emitted when a transfer fails because the recipient cannot pay the deposit, with
+        /// the index of the error in this pallet. [from, to, claim_hash, reason_code, seq]
+        ClaimTransferFailed(AccountId, AccountId, ClaimHash, u8, u64),
+        /// Event emitted when a batch is rejected because of the claim at an index. [index]
+        BatchRejected(u32),
+        /// Event emitted when an account tries to revoke a claim it is not authorized to revoke.
+        /// [who, claim_hash, seq]
+        UnauthorizedRevokeAttempt(AccountId, ClaimHash, u64),
+        /// Diagnostic event emitted when a claim is revoked in the block it was created in, which
+        /// usually means two clients raced for it. [who, claim_hash, seq]
+        SameBlockConflict(AccountId, ClaimHash, u64),
+        /// Event emitted when an owner places a claim in escrow. [who, claim_hash, seq]
+        ClaimEscrowed(AccountId, ClaimHash, u64),
+        /// Event emitted when a claim is released from escrow. [claim_hash, dest, seq]
+        ClaimReleased(ClaimHash, AccountId, u64),
+        /// Event emitted after `ClaimCreated` with the raw bytes of the claim, unless
+        /// `EmitHashedEventsOnly` is set. [claim_hash, claim, seq]
+        ClaimBytes(ClaimHash, Vec<u8>, u64),
+        /// Event emitted when the owner gives a claim an alias. [claim_hash, alias, seq]
+        AliasSet(ClaimHash, Vec<u8>, u64),
+        /// Event emitted when the bond of an open challenge is returned because `ForceOrigin`
+        /// revoked the claim. [challenger, claim_hash, bond, seq]
+        ChallengeRefunded(AccountId, ClaimHash, Balance, u64),
     }
 );
 
@@ -52,6 +659,109 @@ decl_error! {
         ProofAlreadyExist,
         ClaimNotExist,
         NotClaimOwner,
+        /// The claim is shorter than `MinClaimLength`.
+        ClaimTooShort,
+        /// The claim is longer than `MaxClaimLength`.
+        ClaimTooLong,
+        /// The account already owns `MaxClaimsPerAccount` claims.
+        TooManyClaims,
+        /// The account cannot afford the claim deposit, or the deposit and fee when creating a claim,
+        /// or the challenge bond.
+        InsufficientBalanceForDeposit,
+        /// The recipient of a transfer is already the owner of the claim.
+        CannotTransferToSelf,
+        /// The metadata is longer than `MaxMetadataLength`.
+        MetadataTooLong,
+        /// The claim header version is higher than `MaxClaimVersion`.
+        UnsupportedClaimVersion,
+        /// The signature does not prove that the owner authorized the claim with their next nonce.
+        InvalidSignature,
+        /// No cosignature was given for a claim that requires one.
+        MissingCosignature,
+        /// The cosignature does not prove that the cosigner approved the claim with their next nonce.
+        InvalidCosignature,
+        /// The nonce was already used to create a different claim.
+        NonceReused,
+        /// The URI is longer than `MaxUriLength`.
+        UriTooLong,
+        /// The revocation reason is longer than `MaxReasonLength`.
+        ReasonTooLong,
+        /// No transfer of the claim has been proposed.
+        NoPendingTransfer,
+        /// The sender is not the recipient of the proposed transfer.
+        NotProposedRecipient,
+        /// The claim has no expiry or its expiry block has not been reached.
+        ClaimNotExpired,
+        /// The sender is neither the owner of the claim nor an operator approved by the owner.
+        NotAuthorized,
+        /// The pallet is paused.
+        Paused,
+        /// There are already `MaxTotalProofs` claims on chain.
+        ProofLimitReached,
+        /// The claim is locked until a later block.
+        ClaimLocked,
+        /// A lock may only be extended, not shortened.
+        CannotShortenLock,
+        /// The expiry would be more than `MaxExpiry` blocks from now.
+        ExpiryTooLong,
+        /// The claim was created without an expiry.
+        NoExpiry,
+        /// Auto-renew is not switched on for the claim.
+        AutoRenewOff,
+        /// The claim already has an open challenge.
+        AlreadyChallenged,
+        /// The claim has no open challenge.
+        NoChallenge,
+        /// The challenge can no longer be resolved.
+        ChallengeWindowClosed,
+        /// The challenge can still be resolved.
+        ChallengeWindowOpen,
+        /// Only the challenger may withdraw a challenge.
+        NotChallenger,
+        /// A block number calculation overflowed.
+        ArithmeticOverflow,
+        /// The claim is frozen.
+        ClaimFrozen,
+        /// The claim has a challenge that can still be resolved.
+        ClaimChallenged,
+        /// The claim is sealed.
+        ClaimSealed,
+        /// The same claim appears more than once in a batch.
+        DuplicateInBatch,
+        /// The account is already an owner of the claim.
+        AlreadyOwner,
+        /// The account is not an owner of the claim.
+        NotAnOwner,
+        /// The claim already has `MaxOwners` owners.
+        TooManyOwners,
+        /// The only owner of a claim cannot be removed.
+        LastOwner,
+        /// More than `MaxQueryBatch` claims were queried at once.
+        QueryBatchTooLarge,
+        /// The claim was created less than `MinClaimAge` blocks ago.
+        ClaimTooYoung,
+        /// The account already created `MaxClaimsPerBlock` claims in this block.
+        RateLimited,
+        /// The account is not allowlisted to create claims.
+        NotAllowed,
+        /// Claims may only be moved to the escrow account with `escrow_claim`.
+        CannotTransferToEscrow,
+        /// The claim is not in escrow.
+        NotEscrowed,
+        /// The alias is longer than `MaxAliasLength`.
+        AliasTooLong,
+        /// The alias already resolves to another claim.
+        AliasTaken,
+        /// The claim was not created in the expected block, so it changed since it was read.
+        ClaimChanged,
+        /// More than `MaxBatch` claims were given to a batch call.
+        BatchTooLarge,
+        /// Expired claims cannot be removed while a storage migration is in progress.
+        MigrationInProgress,
+        /// The block already records `MaxCreatedPerBlock` new claims.
+        BlockClaimsFull,
+        /// The alias is empty.
+        AliasEmpty,
     }
 }
 
@@ -63,76 +773,2565 @@ decl_module! {
         // Errors must be initialized if they are used by the pallet.
         type Error = Error<T>;
 
-        // Events must be initialized if they are used by the pallet.
-        fn deposit_event() = default;
+        /// The minimum length in bytes of a claim.
+        const MinClaimLength: u32 = T::MinClaimLength::get();
+
+        /// The maximum length in bytes of a claim.
+        const MaxClaimLength: u32 = T::MaxClaimLength::get();
+
+        /// The maximum number of claims a single account may own.
+        const MaxClaimsPerAccount: u32 = T::MaxClaimsPerAccount::get();
+
+        /// The maximum number of claims a single account may create in one block.
+        const MaxClaimsPerBlock: u32 = T::MaxClaimsPerBlock::get();
+
+        /// The maximum number of claims created in one block.
+        const MaxCreatedPerBlock: u32 = T::MaxCreatedPerBlock::get();
+
+        /// The maximum number of claims in a single batch call.
+        const MaxBatch: u32 = T::MaxBatch::get();
+
+        /// The maximum number of claims on chain across all accounts.
+        const MaxTotalProofs: u32 = T::MaxTotalProofs::get();
+
+        /// The amount reserved from the owner of each claim.
+        const ClaimDeposit: BalanceOf<T> = T::ClaimDeposit::get();
+
+        /// The fee for creating a claim in an empty store.
+        const FeeBase: BalanceOf<T> = T::FeeBase::get();
+
+        /// How much the fee for creating a claim grows with every claim on chain.
+        const FeeSlope: BalanceOf<T> = T::FeeSlope::get();
+
+        /// The maximum length in bytes of the metadata attached to a claim.
+        const MaxMetadataLength: u32 = T::MaxMetadataLength::get();
+
+        /// The highest claim header version accepted.
+        const MaxClaimVersion: u16 = T::MaxClaimVersion::get();
+
+        // Migrate storage written by older versions of this pallet.
+        fn on_runtime_upgrade() -> Weight {
+            #[cfg(feature = "try-runtime")]
+            migrations::pre_upgrade::<T>().expect("pre-upgrade checks failed");
+
+            #[allow(clippy::let_and_return)]
+            let weight = migrations::migrate_to_v5::<T>();
+
+            #[cfg(feature = "try-runtime")]
+            migrations::post_upgrade::<T>().expect("post-upgrade checks failed");
+
+            weight
+        }
+
+        // Reset the activity counters and carry on with a storage migration that did not fit in
+        // the upgrade block.
+        fn on_initialize(_now: T::BlockNumber) -> Weight {
+            BlockActivity::kill();
+
+            let migration = if MigrationCursor::<T>::exists() {
+                migrations::migrate_to_v5::<T>()
+            } else {
+                0
+            };
+
+            // Account for the counters being reset here and read back in `on_finalize`, for the
+            // sequence number of the activity event, and for checking on the migration.
+            migration.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+        }
+
+        // Prune expired claims with the weight the block has left, then report the claim activity
+        // of the block, if there was any.
+        fn on_finalize(now: T::BlockNumber) {
+            let used = frame_system::Module::<T>::block_weight().total();
+            let remaining = T::MaximumBlockWeight::get().saturating_sub(used).min(Self::prune_budget());
+            let consumed = Self::on_idle(now, remaining);
+            frame_system::Module::<T>::register_extra_weight_unchecked(consumed, DispatchClass::Mandatory);
+
+            let (created, removed, transferred) = BlockActivity::get();
+            if created > 0 || removed > 0 || transferred > 0 {
+                Self::deposit_event(RawEvent::BlockClaimActivity(created, removed, transferred));
+            }
+        }
+
+        // Submit an unsigned transaction revoking the claims that have expired but are still
+        // waiting to be pruned.
+        fn offchain_worker(block_number: T::BlockNumber) {
+            let (expired, _) = Self::expired_claims(block_number);
+            if expired.is_empty() {
+                return;
+            }
+
+            let call = Call::revoke_expired(expired);
+            if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+                frame_support::debug::error!("Failed to submit revoke_expired transaction");
+            }
+        }
 
         // Allow a user to claim ownership of an unclaimed proof.
-        #[weight = 0]
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32)]
         pub fn create_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult{
             // Check that the extrinsic was signed and get the signer.
             // This function will return an error if the extrinsic is not signed.
             let sender = ensure_signed(origin)?;
 
-            // Verify that the specified proof has not already existed.
-            ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            Self::do_create_claim(sender, claim, None)
+        }
+
+        // Allow a user to claim ownership of an unclaimed proof, tagging the call with a `nonce` so
+        // that resubmitting it is harmless: a call with the same nonce as the sender's last one
+        // succeeds without doing anything.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().reads_writes(1, 1))]
+        pub fn create_claim_idempotent(origin, claim: Vec<u8>, nonce: u64) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
 
-            // Store the proof with the sender and block number.
-            Proofs::<T>::insert(&claim, (sender.clone(), frame_system::Module::<T>::block_number()));
+            // A retry of the last call has already taken effect, while reusing its nonce for another
+            // claim is a mistake that must not pass silently.
+            let claim_hash = Self::derive_key(&claim);
+            if let Some((last_nonce, last_hash)) = LastNonceOf::<T>::get(&sender) {
+                if last_nonce == nonce {
+                    ensure!(last_hash == claim_hash, Error::<T>::NonceReused);
+                    return Ok(());
+                }
+            }
 
-            // Emit an event that the claim was created.
-            Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+            Self::do_create_claim(sender.clone(), claim, None)?;
+            LastNonceOf::<T>::insert(&sender, (nonce, claim_hash));
 
             Ok(())
         }
-        
-        // Allow the owner to revoke their claim.
-        #[weight = 0]
-        pub fn revoke_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult{
-            // Check that the extrinsic was signed and get the signer.
-            // This function will return an error if the extrinsic is not signed.
+
+        // Allow a user to claim ownership of an unclaimed proof for `ttl` blocks.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn create_claim_with_expiry(origin, claim: Vec<u8>, ttl: T::BlockNumber) -> dispatch::DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            // Verify that the specified proof has already existed.
-            ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
 
-            // Get owner of the claim.
-            let (owner, _block_number) = Proofs::<T>::get(&claim);
+            // Verify that the expiry is not further away than allowed.
+            ensure!(ttl <= T::MaxExpiry::get(), Error::<T>::ExpiryTooLong);
 
-            // Verify that sender of the current call is the claim owner.
-            ensure!(sender == owner, Error::<T>::NotClaimOwner);
+            let expiry = frame_system::Module::<T>::block_number()
+                .checked_add(&ttl)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+            Self::do_create_claim(sender, claim, Some(expiry))
+        }
+
+        // Allow a user to claim ownership of an unclaimed proof and attach metadata to it.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().writes(1))]
+        pub fn create_claim_with_metadata(origin, claim: Vec<u8>, metadata: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the metadata is not longer than allowed.
+            ensure!(metadata.len() as u32 <= T::MaxMetadataLength::get(), Error::<T>::MetadataTooLong);
 
-            // Remove claim from storage.
-            Proofs::<T>::remove(&claim);
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_create_claim(sender.clone(), claim, None)?;
 
-            // Emit an event that the claim was erased.
-            Self::deposit_event(RawEvent::ClaimRevoked(sender, claim));
+            // Attach the metadata to the new claim.
+            MetadataOf::<T>::insert(&claim_hash, metadata);
+            Self::deposit_event(RawEvent::ClaimMetadataSet(sender, claim_hash, Self::claim_seq()));
 
             Ok(())
         }
 
-        // Transform the claim.
-        #[weight = 0]
-        pub fn transfer_claim(origin, dest: <T as frame_system::Trait>::AccountId, claim: Vec<u8>) -> dispatch::DispatchResult {
+        // Allow a user to claim ownership of an unclaimed proof and store a typed header with it.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().writes(1))]
+        pub fn create_claim_with_header(origin, claim: Vec<u8>, header: ClaimHeader) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the header version is supported.
+            ensure!(header.version <= T::MaxClaimVersion::get(), Error::<T>::UnsupportedClaimVersion);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_create_claim(sender, claim, None)?;
+
+            // Store the header with the new claim.
+            HeaderOf::<T>::insert(&claim_hash, header);
+            Self::deposit_event(RawEvent::ClaimHeaderSet(claim_hash, header, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to anchor a document kept off-chain by the hash of its content, along with
+        // the URI it can be fetched from. Anyone holding the document can verify it by hashing it
+        // and looking the hash up.
+        #[weight = <T as Trait>::WeightInfo::create_claim(0).saturating_add(T::DbWeight::get().writes(1))]
+        pub fn create_anchored(origin, content_hash: ClaimHash<T>, uri: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the URI is not longer than allowed.
+            ensure!(uri.len() as u32 <= T::MaxUriLength::get(), Error::<T>::UriTooLong);
+
+            let len = content_hash.as_ref().len() as u32;
+            Self::do_create_claim_hash(sender.clone(), sender.clone(), content_hash, len, None)?;
+
+            // Attach the URI to the new claim.
+            UriOf::<T>::insert(&content_hash, uri);
+            Self::deposit_event(RawEvent::ClaimAnchored(sender, content_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to claim ownership of an unclaimed proof and file it under `category`.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().writes(2))]
+        pub fn create_claim_categorized(origin, claim: Vec<u8>, category: u8) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_create_claim(sender, claim, None)?;
+
+            // File the new claim under its category.
+            CategoryOf::<T>::insert(&claim_hash, category);
+            ClaimsByCategory::<T>::insert(category, &claim_hash, ());
+            Self::deposit_event(RawEvent::ClaimCategorized(claim_hash, category, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to claim an unclaimed proof on behalf of `owner`. The signer pays the fee and
+        // the deposit, which is returned to them when the claim is revoked or transferred, while
+        // `owner` owns the claim.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().writes(1))]
+        pub fn create_claim_for(origin, claim: Vec<u8>, owner: <T as frame_system::Trait>::AccountId) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_create_claim_for(sender.clone(), owner.clone(), claim, None)?;
+
+            // Emit an event that the claim was created on behalf of `owner`.
+            Self::deposit_event(RawEvent::ClaimCreatedFor(sender, owner, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a relayer to submit a claim that `owner` authorized off-chain by signing
+        // `signed_claim_payload` with their next nonce. The relayer pays the fee and the deposit,
+        // while `owner` owns the claim.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn create_claim_signed(
+            origin,
+            claim: Vec<u8>,
+            owner: <T as frame_system::Trait>::AccountId,
+            signature: T::OffchainSignature,
+        ) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the owner signed this claim with their next nonce.
+            let nonce = SignedClaimNonce::<T>::get(&owner);
+            let payload = Self::signed_claim_payload(&claim, &owner, nonce);
+            ensure!(signature.verify(&payload[..], &owner), Error::<T>::InvalidSignature);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_create_claim_for(sender.clone(), owner.clone(), claim, None)?;
+            SignedClaimNonce::<T>::insert(&owner, nonce.saturating_add(1));
+
+            // Emit an event that the claim was created on behalf of `owner`.
+            Self::deposit_event(RawEvent::ClaimCreatedFor(sender, owner, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to claim an unclaimed proof under dual control: `cosigner` approves it by
+        // signing `cosigned_claim_payload` with their next nonce, and both become its owners. The
+        // sender pays the fee and the deposit.
+        #[weight = <T as Trait>::WeightInfo::create_claim(claim.len() as u32).saturating_add(T::DbWeight::get().reads_writes(2, 2))]
+        pub fn create_claim_cosigned(
+            origin,
+            claim: Vec<u8>,
+            cosigner: <T as frame_system::Trait>::AccountId,
+            cosign_sig: Option<T::OffchainSignature>,
+        ) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the cosigner is someone else and that the claim may have two owners.
+            ensure!(cosigner != sender, Error::<T>::AlreadyOwner);
+            ensure!(T::MaxOwners::get() >= 2, Error::<T>::TooManyOwners);
+
+            // Verify that the cosigner approved this claim by this sender with their next nonce.
+            let cosign_sig = cosign_sig.ok_or(Error::<T>::MissingCosignature)?;
+            let nonce = SignedClaimNonce::<T>::get(&cosigner);
+            let payload = Self::cosigned_claim_payload(&claim, &sender, nonce);
+            ensure!(cosign_sig.verify(&payload[..], &cosigner), Error::<T>::InvalidCosignature);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_create_claim(sender.clone(), claim, None)?;
+            SignedClaimNonce::<T>::insert(&cosigner, nonce.saturating_add(1));
+
+            // Record the cosigner as a co-owner.
+            OwnersOf::<T>::mutate(&claim_hash, |owners| owners.push(cosigner.clone()));
+
+            // Emit an event that the claim was cosigned.
+            Self::deposit_event(RawEvent::ClaimCosigned(sender, cosigner, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to claim ownership of many unclaimed proofs at once.
+        //
+        // If `best_effort` is set, claims that cannot be created (for instance because they
+        // already exist) are skipped. Otherwise the first such claim fails the whole batch, no
+        // claim is created and `BatchRejected` names the index of the failing claim.
+        #[weight = <T as Trait>::WeightInfo::create_claims(claims.len() as u32)]
+        pub fn create_claims(origin, claims: Vec<Vec<u8>>, best_effort: bool) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the batch is no larger than its weight was benchmarked for.
+            ensure!(claims.len() as u32 <= T::MaxBatch::get(), Error::<T>::BatchTooLarge);
+
+            // Reject a batch naming the same claim twice before creating any of it.
+            if !best_effort {
+                if let Some(index) = Self::first_duplicate(&claims) {
+                    Self::deposit_event(RawEvent::BatchRejected(index));
+                    return Err(Error::<T>::DuplicateInBatch.into());
+                }
+            }
+
+            let count = Self::do_create_claims(sender, claims, best_effort).map_err(|(index, e)| {
+                Self::deposit_event(RawEvent::BatchRejected(index));
+                e
+            })?;
+
+            // Emit an event summarising the batch.
+            Self::deposit_event(RawEvent::BatchClaimsCreated(count));
+
+            Ok(())
+        }
+
+        // Allow the owner, or an operator they approved, to transfer many claims to `dest` at once,
+        // for instance when rotating keys.
+        //
+        // If `best_effort` is set, claims that cannot be transferred (for instance because the
+        // sender may not move them) are skipped. Otherwise the first such claim fails the whole
+        // batch, no claim is transferred and `BatchRejected` names the index of the failing claim.
+        #[weight = <T as Trait>::WeightInfo::transfer_claims(claims.len() as u32)]
+        pub fn transfer_claims(
+            origin,
+            claims: Vec<Vec<u8>>,
+            dest: <T as frame_system::Trait>::AccountId,
+            best_effort: bool,
+        ) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the batch is no larger than its weight was benchmarked for.
+            ensure!(claims.len() as u32 <= T::MaxBatch::get(), Error::<T>::BatchTooLarge);
+
+            let count = Self::do_transfer_claims(sender, claims, dest.clone(), best_effort).map_err(|(index, e)| {
+                Self::deposit_event(RawEvent::BatchRejected(index));
+                e
+            })?;
+
+            // Emit an event summarising the batch.
+            Self::deposit_event(RawEvent::BatchClaimsTransfered(dest, count));
+
+            Ok(())
+        }
+
+        // Allow the owner, or an operator they approved, to revoke their claim.
+        //
+        // Revoking frees state, so a successful revocation is free of charge. Failed attempts
+        // still pay the fee.
+        #[weight = <T as Trait>::WeightInfo::revoke_claim(claim.len() as u32)]
+        pub fn revoke_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResultWithPostInfo {
             // Check that the extrinsic was signed and get the signer.
             // This function will return an error if the extrinsic is not signed.
             let sender = ensure_signed(origin)?;
 
-            // Verify that the specified proof has already existed.
-            ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
 
-            // Get owner of the claim.
-            let (owner, block_number) = Proofs::<T>::get(&claim);
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
 
-            // Verify that sender of the current call is the claim owner.
-            ensure!(sender == owner, Error::<T>::NotClaimOwner);
+            Self::do_revoke_claim(sender, Self::derive_key(&claim))?;
+
+            Ok(Pays::No.into())
+        }
+
+        // Allow the sender to revoke every claim they own. At most `MaxRevokeAll` claims are removed
+        // per call; call again to remove the rest.
+        #[weight = <T as Trait>::WeightInfo::revoke_all(T::MaxRevokeAll::get())]
+        pub fn revoke_all(origin) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Collect the claims first, as removing them while iterating the index is undefined.
+            // Frozen and locked claims are left in place.
+            let claims: Vec<ClaimHash<T>> = ClaimsByOwner::<T>::iter_prefix(&sender)
+                .map(|(claim_hash, ())| claim_hash)
+                .filter(|claim_hash| Self::ensure_movable(claim_hash).is_ok() && !Self::is_sealed_against_revoke(claim_hash))
+                .take(T::MaxRevokeAll::get() as usize)
+                .collect();
+
+            for claim_hash in claims.iter() {
+                if let Ok(mut info) = Self::claim_info(claim_hash) {
+                    Self::take_late_revoke_penalty(claim_hash, &mut info);
+                    Self::remove_claim(claim_hash, &info);
+                }
+            }
+
+            // Emit an event with the number of claims that were erased.
+            Self::deposit_event(RawEvent::AllClaimsRevoked(sender, claims.len() as u32));
+
+            Ok(())
+        }
+
+        // Allow `ForceOrigin` to revoke any claim regardless of its owner.
+        #[weight = <T as Trait>::WeightInfo::revoke_claim(claim.len() as u32)]
+        pub fn force_revoke(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let claim_hash = Self::derive_key(&claim);
 
-            // Change the owner of the claim.
-            Proofs::<T>::insert(&claim, (&dest, &block_number));
+            // Get the claim, verifying that the specified proof has already existed.
+            let info = Self::claim_info(&claim_hash)?;
 
-            // Emit an event that the claim was changed.
-            Self::deposit_event(RawEvent::ClaimTransfered(sender, dest, claim));
+            // An open challenge is moot once the claim is gone, so the challenger gets the bond back.
+            if let Some((challenger, _, bond)) = Challenges::<T>::take(&claim_hash) {
+                T::Currency::unreserve(&challenger, bond);
+                Self::deposit_event(RawEvent::ChallengeRefunded(challenger, claim_hash, bond, Self::claim_seq()));
+            }
 
-			Ok(())
+            // Remove claim from storage along with any lock or freeze, and return the deposit to its
+            // owner.
+            Self::remove_claim(&claim_hash, &info);
+
+            // Emit an event that the claim was erased by `ForceOrigin`.
+            Self::deposit_event(RawEvent::ClaimForceRevoked(claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to replace the metadata of their claim.
+        #[weight = <T as Trait>::WeightInfo::set_claim_metadata(claim.len() as u32, metadata.len() as u32)]
+        pub fn set_claim_metadata(origin, claim: Vec<u8>, metadata: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim and metadata are not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+            ensure!(metadata.len() as u32 <= T::MaxMetadataLength::get(), Error::<T>::MetadataTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            MetadataOf::<T>::insert(&claim_hash, metadata);
+
+            // Emit an event that the metadata was set.
+            Self::deposit_event(RawEvent::ClaimMetadataSet(sender, claim_hash, Self::claim_seq()));
+
+            Ok(())
         }
+
+        // Allow the owner to give their claim a human-readable alias, replacing any earlier one.
+        #[weight = <T as Trait>::WeightInfo::set_alias(claim.len() as u32, alias.len() as u32)]
+        pub fn set_alias(origin, claim: Vec<u8>, alias: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim and alias are not longer than allowed, and that the alias is
+            // not empty.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+            ensure!(!alias.is_empty(), Error::<T>::AliasEmpty);
+            ensure!(alias.len() as u32 <= T::MaxAliasLength::get(), Error::<T>::AliasTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that the alias is free or already names this claim.
+            if let Some(existing) = AliasToClaim::<T>::get(&alias) {
+                ensure!(existing == claim_hash, Error::<T>::AliasTaken);
+            }
+
+            if let Some(previous) = AliasOf::<T>::get(&claim_hash) {
+                AliasToClaim::<T>::remove(previous);
+            }
+            AliasToClaim::<T>::insert(&alias, &claim_hash);
+            AliasOf::<T>::insert(&claim_hash, &alias);
+
+            // Emit an event that the alias was set.
+            Self::deposit_event(RawEvent::AliasSet(claim_hash, alias, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow `ForceOrigin` to create a claim with a given owner and creation block, for instance
+        // when importing an existing registry. Such claims hold no deposit.
+        #[weight = <T as Trait>::WeightInfo::force_create(claim.len() as u32)]
+        pub fn force_create(
+            origin,
+            claim: Vec<u8>,
+            owner: <T as frame_system::Trait>::AccountId,
+            created_at: T::BlockNumber,
+        ) -> dispatch::DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let claim_hash = Self::do_force_create(claim, owner.clone(), created_at)?;
+
+            // Emit an event that the claim was created by `ForceOrigin`.
+            Self::deposit_event(RawEvent::ClaimForceCreated(owner, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow `ForceOrigin` to import many claims at once, as with `force_create`. Entries that already
+        // exist are skipped. Any other failure rejects the whole batch, no claim is imported and
+        // `BatchRejected` names the index of the failing entry.
+        #[weight = entries.iter().fold(0 as Weight, |weight, (claim, _, _)| {
+            weight.saturating_add(<T as Trait>::WeightInfo::force_create(claim.len() as u32))
+        })]
+        pub fn force_create_batch(
+            origin,
+            entries: Vec<(Vec<u8>, <T as frame_system::Trait>::AccountId, T::BlockNumber)>,
+        ) -> dispatch::DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let count = Self::do_force_create_batch(entries).map_err(|(index, e)| {
+                Self::deposit_event(RawEvent::BatchRejected(index));
+                e
+            })?;
+
+            // Emit an event with the number of claims imported.
+            Self::deposit_event(RawEvent::ClaimsForceImported(count));
+
+            Ok(())
+        }
+
+        // Allow Root to pause or unpause all state-changing extrinsics.
+        #[weight = <T as Trait>::WeightInfo::set_paused()]
+        pub fn set_paused(origin, paused: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+
+            Paused::put(paused);
+
+            // Emit an event that the pause switch changed.
+            Self::deposit_event(RawEvent::PauseSet(paused));
+
+            Ok(())
+        }
+
+        // Allow Root to approve an account to create claims.
+        #[weight = <T as Trait>::WeightInfo::add_to_allowlist()]
+        pub fn add_to_allowlist(origin, who: <T as frame_system::Trait>::AccountId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+
+            Allowlist::<T>::insert(&who, true);
+
+            // Emit an event that the account was allowlisted.
+            Self::deposit_event(RawEvent::AddedToAllowlist(who));
+
+            Ok(())
+        }
+
+        // Allow Root to withdraw an account's approval to create claims. Claims it already owns
+        // are unaffected.
+        #[weight = <T as Trait>::WeightInfo::remove_from_allowlist()]
+        pub fn remove_from_allowlist(origin, who: <T as frame_system::Trait>::AccountId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+
+            Allowlist::<T>::remove(&who);
+
+            // Emit an event that the account was removed from the allowlist.
+            Self::deposit_event(RawEvent::RemovedFromAllowlist(who));
+
+            Ok(())
+        }
+
+        // Allow the owner, or an operator they approved, to transfer their claim.
+        #[weight = <T as Trait>::WeightInfo::transfer_claim(claim.len() as u32)]
+        pub fn transfer_claim(origin, dest: <T as frame_system::Trait>::AccountId, claim: Vec<u8>) -> dispatch::DispatchResult {
+            // Check that the extrinsic was signed and get the signer.
+            // This function will return an error if the extrinsic is not signed.
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            Self::do_transfer_claim(sender, dest, Self::derive_key(&claim))
+        }
+
+        // Allow the owner, or an operator they approved, to transfer their claim only if it was
+        // created in `expected_created_at`, so that a claim revoked and created again since the
+        // caller read it is not transferred by mistake.
+        #[weight = <T as Trait>::WeightInfo::transfer_claim(claim.len() as u32)]
+        pub fn transfer_claim_if_unchanged(
+            origin,
+            claim: Vec<u8>,
+            dest: <T as frame_system::Trait>::AccountId,
+            expected_created_at: T::BlockNumber,
+        ) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the claim is the one the caller expects.
+            ensure!(Self::claim_info(&claim_hash)?.created_at == expected_created_at, Error::<T>::ClaimChanged);
+
+            Self::do_transfer_claim(sender, dest, claim_hash)
+        }
+
+        // Allow the owner, or an operator they approved, to transfer their claim, optionally wiping
+        // its metadata so that notes of the previous owner do not follow the claim.
+        #[weight = <T as Trait>::WeightInfo::transfer_claim(claim.len() as u32).saturating_add(T::DbWeight::get().writes(1))]
+        pub fn transfer_claim_v2(
+            origin,
+            dest: <T as frame_system::Trait>::AccountId,
+            claim: Vec<u8>,
+            clear_metadata: bool,
+        ) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_transfer_claim(sender, dest, claim_hash)?;
+
+            if clear_metadata {
+                MetadataOf::<T>::remove(&claim_hash);
+            }
+
+            // Emit an event telling whether the metadata was cleared.
+            Self::deposit_event(RawEvent::TransferMetadataHandled(claim_hash, clear_metadata, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to claim ownership of an unclaimed 32-byte hash they computed themselves.
+        // The hash is used as the key directly, so it names the same claim as `create_claim` with
+        // its preimage.
+        #[weight = <T as Trait>::WeightInfo::create_claim(0)]
+        pub fn create_claim_hash(origin, claim_hash: ClaimHash<T>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            let len = claim_hash.as_ref().len() as u32;
+            Self::do_create_claim_hash(sender.clone(), sender, claim_hash, len, None)
+        }
+
+        // Allow the owner, or an operator they approved, to revoke their claim and record why in
+        // `RevocationLog`.
+        #[weight = <T as Trait>::WeightInfo::revoke_claim(claim.len() as u32).saturating_add(T::DbWeight::get().writes(1))]
+        pub fn revoke_claim_with_reason(origin, claim: Vec<u8>, reason: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that neither the claim nor the reason is longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+            ensure!(reason.len() as u32 <= T::MaxReasonLength::get(), Error::<T>::ReasonTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::do_revoke_claim(sender.clone(), claim_hash)?;
+
+            // Record the reason, which outlives the claim.
+            let now = frame_system::Module::<T>::block_number();
+            RevocationLog::<T>::insert(&claim_hash, (sender.clone(), now, reason.clone()));
+            Self::deposit_event(RawEvent::ClaimRevokedWithReason(sender, claim_hash, reason, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner or one of their operators to revoke a claim by its hash.
+        #[weight = <T as Trait>::WeightInfo::revoke_claim(0)]
+        pub fn revoke_claim_hash(origin, claim_hash: ClaimHash<T>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            Self::do_revoke_claim(sender, claim_hash)
+        }
+
+        // Allow the owner or one of their operators to transfer a claim by its hash.
+        #[weight = <T as Trait>::WeightInfo::transfer_claim(0)]
+        pub fn transfer_claim_hash(origin, dest: <T as frame_system::Trait>::AccountId, claim_hash: ClaimHash<T>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            Self::do_transfer_claim(sender, dest, claim_hash)
+        }
+
+        // Allow anyone to take over a claim whose expiry block has been reached, without waiting
+        // for it to be pruned.
+        #[weight = <T as Trait>::WeightInfo::reclaim_expired(claim.len() as u32)]
+        pub fn reclaim_expired(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Get the claim, verifying that the specified proof has already existed.
+            let info = Self::claim_info(&claim_hash)?;
+
+            // Verify that the claim has expired, and that no migration is in progress.
+            ensure!(!MigrationCursor::<T>::exists(), Error::<T>::MigrationInProgress);
+            let now = frame_system::Module::<T>::block_number();
+            let expiry = ExpiryOf::<T>::get(&claim_hash).ok_or(Error::<T>::ClaimNotExpired)?;
+            ensure!(expiry <= now, Error::<T>::ClaimNotExpired);
+
+            // Verify that the claim is neither frozen, locked nor challenged.
+            Self::ensure_movable(&claim_hash)?;
+
+            Self::do_reclaim_expired(sender.clone(), &claim_hash, &info, claim.len() as u32)?;
+
+            // Emit an event that the claim was reclaimed.
+            Self::deposit_event(RawEvent::ClaimReclaimed(sender, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to move their claim to a new claim, keeping its owner, creator, creation
+        // time, deposit and everything stored alongside it.
+        #[weight = <T as Trait>::WeightInfo::rename_claim((old_claim.len() + new_claim.len()) as u32)]
+        pub fn rename_claim(origin, old_claim: Vec<u8>, new_claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            let (old_hash, new_hash) = Self::do_rename_claim(&sender, &old_claim, &new_claim)?;
+
+            // Emit an event that the claim was renamed.
+            Self::deposit_event(RawEvent::ClaimRenamed(sender, old_hash, new_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to correct a mistyped claim by replacing it with the right one. The new
+        // claim keeps the owner, creation time, deposit and everything else of the old one, which
+        // is removed, so nothing is lost the way it would be by revoking and creating again. This
+        // moves the claim just like `rename_claim`, but is the documented path for corrections.
+        #[weight = <T as Trait>::WeightInfo::rename_claim((old_claim.len() + new_claim.len()) as u32)]
+        pub fn replace_claim(origin, old_claim: Vec<u8>, new_claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            let (old_hash, new_hash) = Self::do_rename_claim(&sender, &old_claim, &new_claim)?;
+
+            // Emit an event that the claim was replaced.
+            Self::deposit_event(RawEvent::ClaimReplaced(sender, old_hash, new_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to lock their claim against revocation and transfer until block `until`.
+        // An existing lock may be extended but not shortened.
+        #[weight = <T as Trait>::WeightInfo::lock_claim(claim.len() as u32)]
+        pub fn lock_claim(origin, claim: Vec<u8>, until: T::BlockNumber) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that any existing lock is not shortened.
+            if let Some(current) = LockedUntil::<T>::get(&claim_hash) {
+                ensure!(until >= current, Error::<T>::CannotShortenLock);
+            }
+
+            LockedUntil::<T>::insert(&claim_hash, until);
+
+            // Emit an event that the claim was locked.
+            Self::deposit_event(RawEvent::ClaimLockSet(sender, claim_hash, until, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to push back the expiry of their claim by `extra_blocks`, up to
+        // `MaxExpiry` blocks from now.
+        #[weight = <T as Trait>::WeightInfo::extend_expiry(claim.len() as u32)]
+        pub fn extend_expiry(origin, claim: Vec<u8>, extra_blocks: T::BlockNumber) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that the claim expires and that the new expiry is not further away than allowed.
+            let expiry = ExpiryOf::<T>::get(&claim_hash).ok_or(Error::<T>::NoExpiry)?;
+            let new_expiry = expiry.checked_add(&extra_blocks).ok_or(Error::<T>::ArithmeticOverflow)?;
+            let now = frame_system::Module::<T>::block_number();
+            ensure!(new_expiry <= now.saturating_add(T::MaxExpiry::get()), Error::<T>::ExpiryTooLong);
+
+            Self::set_expiry(&claim_hash, new_expiry);
+
+            // Emit an event that the expiry was extended.
+            Self::deposit_event(RawEvent::ClaimExpiryExtended(claim_hash, new_expiry, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner of an expiring claim to switch auto-renew on with a time to live of `ttl`
+        // blocks, up to `MaxExpiry`, or off with `None`.
+        #[weight = <T as Trait>::WeightInfo::set_auto_renew(claim.len() as u32)]
+        pub fn set_auto_renew(origin, claim: Vec<u8>, ttl: Option<T::BlockNumber>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that the claim expires and that the time to live is not longer than allowed.
+            ensure!(ExpiryOf::<T>::contains_key(&claim_hash), Error::<T>::NoExpiry);
+            match ttl {
+                Some(ttl) => {
+                    ensure!(ttl <= T::MaxExpiry::get(), Error::<T>::ExpiryTooLong);
+                    AutoRenewOf::<T>::insert(&claim_hash, ttl);
+                }
+                None => AutoRenewOf::<T>::remove(&claim_hash),
+            }
+
+            // Emit an event that auto-renew was set.
+            Self::deposit_event(RawEvent::ClaimAutoRenewSet(claim_hash, ttl, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner of a claim with auto-renew switched on to reset its expiry to its time to
+        // live from now.
+        #[weight = <T as Trait>::WeightInfo::touch_claim(claim.len() as u32)]
+        pub fn touch_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that auto-renew is switched on.
+            let ttl = AutoRenewOf::<T>::get(&claim_hash).ok_or(Error::<T>::AutoRenewOff)?;
+            let new_expiry = frame_system::Module::<T>::block_number()
+                .checked_add(&ttl)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+            Self::set_expiry(&claim_hash, new_expiry);
+
+            // Emit an event that the expiry was extended.
+            Self::deposit_event(RawEvent::ClaimExpiryExtended(claim_hash, new_expiry, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow anyone to contest a claim by reserving `ChallengeBond`. The challenge may be
+        // resolved by `ResolveOrigin` for `ChallengePeriod` blocks.
+        #[weight = <T as Trait>::WeightInfo::challenge_claim(claim.len() as u32)]
+        pub fn challenge_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and is not already challenged.
+            Self::claim_info(&claim_hash)?;
+            ensure!(!Challenges::<T>::contains_key(&claim_hash), Error::<T>::AlreadyChallenged);
+
+            let end = frame_system::Module::<T>::block_number()
+                .checked_add(&T::ChallengePeriod::get())
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+            // Reserve the bond from the challenger.
+            let bond = T::ChallengeBond::get();
+            T::Currency::reserve(&sender, bond)
+                .map_err(|_| Error::<T>::InsufficientBalanceForDeposit)?;
+
+            Challenges::<T>::insert(&claim_hash, (sender.clone(), end, bond));
+
+            // Emit an event that the claim was challenged.
+            Self::deposit_event(RawEvent::ClaimChallenged(sender, claim_hash, end, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow anyone to remove a claim whose expiry block has been reached, paying them
+        // `CleanupReward` out of its deposit and returning the rest to the owner.
+        #[weight = <T as Trait>::WeightInfo::revoke_if_expired(claim.len() as u32)]
+        pub fn revoke_if_expired(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Get the claim, verifying that the specified proof has already existed.
+            let mut info = Self::claim_info(&claim_hash)?;
+
+            // Verify that the claim has expired, and that no migration is in progress.
+            ensure!(!MigrationCursor::<T>::exists(), Error::<T>::MigrationInProgress);
+            let now = frame_system::Module::<T>::block_number();
+            let expiry = ExpiryOf::<T>::get(&claim_hash).ok_or(Error::<T>::ClaimNotExpired)?;
+            ensure!(expiry <= now, Error::<T>::ClaimNotExpired);
+
+            // Verify that the claim is neither frozen, locked nor challenged.
+            Self::ensure_movable(&claim_hash)?;
+
+            // Pay the reward out of the deposit, then remove the claim and refund what is left.
+            let depositor = DepositorOf::<T>::get(&claim_hash).unwrap_or_else(|| info.owner.clone());
+            let due = T::CleanupReward::get().min(info.deposit);
+            let unmoved = T::Currency::repatriate_reserved(&depositor, &sender, due, BalanceStatus::Free)
+                .unwrap_or(due);
+            let reward = due.saturating_sub(unmoved);
+            info.deposit = info.deposit.saturating_sub(reward);
+            Self::remove_claim(&claim_hash, &info);
+
+            // Emit events that the claim expired and the sender was rewarded.
+            Self::deposit_event(RawEvent::ClaimExpired(info.owner, claim_hash, Self::claim_seq()));
+            Self::deposit_event(RawEvent::CleanupRewarded(sender, claim_hash, reward, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow `ResolveOrigin` to settle an open challenge. If `uphold` is set the claim is revoked,
+        // its deposit slashed to the treasury and the bond returned to the challenger, otherwise the
+        // bond is slashed.
+        #[weight = <T as Trait>::WeightInfo::resolve_challenge(claim.len() as u32)]
+        pub fn resolve_challenge(origin, claim: Vec<u8>, uphold: bool) -> dispatch::DispatchResult {
+            T::ResolveOrigin::ensure_origin(origin)?;
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the claim has a challenge that is still open.
+            let (challenger, end, bond) = Challenges::<T>::get(&claim_hash).ok_or(Error::<T>::NoChallenge)?;
+            let now = frame_system::Module::<T>::block_number();
+            ensure!(now <= end, Error::<T>::ChallengeWindowClosed);
+
+            // Verify that an upheld challenge still has a claim to revoke, before anything changes.
+            let info = if uphold { Some(Self::claim_info(&claim_hash)?) } else { None };
+
+            Challenges::<T>::remove(&claim_hash);
+            if let Some(mut info) = info {
+                T::Currency::unreserve(&challenger, bond);
+
+                // The claim was fraudulent, so its deposit goes to the treasury.
+                let depositor = DepositorOf::<T>::get(&claim_hash).unwrap_or_else(|| info.owner.clone());
+                let (slashed, _) = T::Currency::slash_reserved(&depositor, info.deposit);
+                let amount = slashed.peek();
+                T::Currency::resolve_creating(&Self::treasury_account(), slashed);
+                info.deposit = Zero::zero();
+                Self::remove_claim(&claim_hash, &info);
+                Self::deposit_event(RawEvent::ClaimSlashed(info.owner, amount, Self::claim_seq()));
+            } else {
+                let _ = T::Currency::slash_reserved(&challenger, bond);
+            }
+
+            // Emit an event that the challenge was resolved.
+            Self::deposit_event(RawEvent::ChallengeResolved(claim_hash, uphold, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the challenger to take back their bond once a challenge was left unresolved.
+        #[weight = <T as Trait>::WeightInfo::withdraw_challenge(claim.len() as u32)]
+        pub fn withdraw_challenge(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the sender made the challenge and that it can no longer be resolved.
+            let (challenger, end, bond) = Challenges::<T>::get(&claim_hash).ok_or(Error::<T>::NoChallenge)?;
+            ensure!(sender == challenger, Error::<T>::NotChallenger);
+            let now = frame_system::Module::<T>::block_number();
+            ensure!(now > end, Error::<T>::ChallengeWindowOpen);
+
+            Challenges::<T>::remove(&claim_hash);
+            T::Currency::unreserve(&sender, bond);
+
+            // Emit an event that the challenge was withdrawn.
+            Self::deposit_event(RawEvent::ChallengeWithdrawn(sender, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to seal their claim, so that it can never be transferred or renamed, nor
+        // revoked if `SealBlocksRevoke` is set. There is no way to unseal a claim.
+        #[weight = <T as Trait>::WeightInfo::seal_claim(claim.len() as u32)]
+        pub fn seal_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            Sealed::<T>::insert(&claim_hash, true);
+
+            // Emit an event that the claim was sealed.
+            Self::deposit_event(RawEvent::ClaimSealed(claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner or Root to freeze a claim so that it can be neither revoked nor
+        // transferred until it is unfrozen.
+        #[weight = <T as Trait>::WeightInfo::freeze_claim(claim.len() as u32)]
+        pub fn freeze_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::ensure_owner_or_root(origin, &claim_hash)?;
+
+            Frozen::<T>::insert(&claim_hash, true);
+
+            // Emit an event that the claim was frozen.
+            Self::deposit_event(RawEvent::ClaimFrozen(claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner or Root to unfreeze a claim.
+        #[weight = <T as Trait>::WeightInfo::unfreeze_claim(claim.len() as u32)]
+        pub fn unfreeze_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            Self::ensure_owner_or_root(origin, &claim_hash)?;
+
+            Frozen::<T>::remove(&claim_hash);
+
+            // Emit an event that the claim was unfrozen.
+            Self::deposit_event(RawEvent::ClaimUnfrozen(claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow any owner of a claim to share its ownership with another account.
+        #[weight = <T as Trait>::WeightInfo::add_owner(claim.len() as u32)]
+        pub fn add_owner(origin, claim: Vec<u8>, owner: T::AccountId) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            let info = Self::claim_info(&claim_hash)?;
+            let mut owners = Self::owners_of(&claim_hash, &info);
+
+            // Verify that sender of the current call is an owner of the claim.
+            ensure!(owners.contains(&sender), Error::<T>::NotClaimOwner);
+
+            // Verify that the new owner is not listed yet and that there is room for them.
+            ensure!(!owners.contains(&owner), Error::<T>::AlreadyOwner);
+            ensure!((owners.len() as u32) < T::MaxOwners::get(), Error::<T>::TooManyOwners);
+
+            // Verify that the claim is neither frozen nor locked.
+            Self::ensure_movable(&claim_hash)?;
+
+            owners.push(owner.clone());
+            OwnersOf::<T>::insert(&claim_hash, owners);
+
+            // Emit an event that the owner was added.
+            Self::deposit_event(RawEvent::OwnerAdded(claim_hash, owner, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow any owner of a claim to remove one of its owners, themselves included.
+        //
+        // If the owner holding the deposit is removed, the claim and its deposit pass to the
+        // first remaining owner, as if it had been transferred to them.
+        #[weight = <T as Trait>::WeightInfo::remove_owner(claim.len() as u32)]
+        pub fn remove_owner(origin, claim: Vec<u8>, owner: T::AccountId) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            let info = Self::claim_info(&claim_hash)?;
+            let mut owners = Self::owners_of(&claim_hash, &info);
+
+            // Verify that sender of the current call is an owner of the claim.
+            ensure!(owners.contains(&sender), Error::<T>::NotClaimOwner);
+
+            // Verify that the account is an owner and not the only one.
+            let position = owners.iter().position(|listed| *listed == owner).ok_or(Error::<T>::NotAnOwner)?;
+            ensure!(owners.len() > 1, Error::<T>::LastOwner);
+
+            // Verify that the claim is neither frozen nor locked.
+            Self::ensure_movable(&claim_hash)?;
+
+            owners.remove(position);
+            if owner == info.owner {
+                Self::do_transfer(&claim_hash, info, owners[0].clone())?;
+            }
+            OwnersOf::<T>::insert(&claim_hash, owners);
+
+            // Emit an event that the owner was removed.
+            Self::deposit_event(RawEvent::OwnerRemoved(claim_hash, owner, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the owner to place their claim in escrow, moving it to the escrow account until
+        // `EscrowOrigin` releases it. The deposit stays reserved from the owner meanwhile.
+        #[weight = <T as Trait>::WeightInfo::escrow_claim(claim.len() as u32)]
+        pub fn escrow_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+            let info = Self::claim_info(&claim_hash)?;
+
+            // Verify that sender of the current call is the claim owner.
+            ensure!(info.owner == sender, Error::<T>::NotClaimOwner);
+
+            Self::do_transfer(&claim_hash, info, Self::escrow_account())?;
+            Escrowed::<T>::insert(&claim_hash, &sender);
+
+            // Emit an event that the claim was placed in escrow.
+            Self::deposit_event(RawEvent::ClaimEscrowed(sender, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow `EscrowOrigin` to release a claim from escrow to `dest`, who takes over its deposit.
+        #[weight = <T as Trait>::WeightInfo::release_claim(claim.len() as u32)]
+        pub fn release_claim(origin, claim: Vec<u8>, dest: T::AccountId) -> dispatch::DispatchResult {
+            T::EscrowOrigin::ensure_origin(origin)?;
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the claim is in escrow.
+            ensure!(Escrowed::<T>::contains_key(&claim_hash), Error::<T>::NotEscrowed);
+            let info = Self::claim_info(&claim_hash)?;
+
+            Self::do_transfer(&claim_hash, info, dest.clone())?;
+
+            // Emit an event that the claim was released.
+            Self::deposit_event(RawEvent::ClaimReleased(claim_hash, dest, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow a user to approve or disapprove an operator that may revoke and transfer their
+        // claims.
+        #[weight = <T as Trait>::WeightInfo::set_operator()]
+        pub fn set_operator(origin, operator: <T as frame_system::Trait>::AccountId, approved: bool) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            if approved {
+                Operators::<T>::insert(&sender, &operator, true);
+            } else {
+                Operators::<T>::remove(&sender, &operator);
+            }
+
+            // Emit an event that the approval changed.
+            Self::deposit_event(RawEvent::OperatorSet(sender, operator, approved));
+
+            Ok(())
+        }
+
+        // Revoke claims whose expiry block has been reached. Submitted as an unsigned transaction
+        // by the off-chain worker; claims that are no longer expired or present, or that are
+        // frozen, locked or challenged, are skipped.
+        #[weight = <T as Trait>::WeightInfo::revoke_expired(claims.len() as u32)]
+        pub fn revoke_expired(origin, claims: Vec<ClaimHash<T>>) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+
+            let now = frame_system::Module::<T>::block_number();
+            for claim_hash in claims.iter() {
+                if ExpiryOf::<T>::get(claim_hash).map_or(false, |expiry| Self::is_prunable(claim_hash, expiry, now)) {
+                    Self::expire_claim(claim_hash);
+                }
+            }
+
+            Ok(())
+        }
+
+        // Allow the owner to propose transferring their claim to `dest`, who must accept it.
+        #[weight = <T as Trait>::WeightInfo::propose_transfer(claim.len() as u32)]
+        pub fn propose_transfer(origin, claim: Vec<u8>, dest: <T as frame_system::Trait>::AccountId) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that the claim would actually change hands.
+            ensure!(dest != sender, Error::<T>::CannotTransferToSelf);
+
+            // Record the proposal, replacing any earlier one.
+            PendingTransfer::<T>::insert(&claim_hash, &dest);
+
+            // Emit an event that the transfer was proposed.
+            Self::deposit_event(RawEvent::TransferProposed(sender, dest, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+
+        // Allow the recipient of a proposed transfer to take ownership of the claim.
+        #[weight = <T as Trait>::WeightInfo::accept_transfer(claim.len() as u32)]
+        pub fn accept_transfer(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the sender is the proposed recipient.
+            let dest = PendingTransfer::<T>::get(&claim_hash).ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(sender == dest, Error::<T>::NotProposedRecipient);
+
+            let info = Proofs::<T>::get(&claim_hash);
+            Self::do_transfer(&claim_hash, info, dest)
+        }
+
+        // Allow the owner to withdraw a proposed transfer of their claim.
+        #[weight = <T as Trait>::WeightInfo::cancel_transfer(claim.len() as u32)]
+        pub fn cancel_transfer(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // Verify that the pallet is not paused.
+            ensure!(!Paused::get(), Error::<T>::Paused);
+
+            // Verify that the claim is not longer than allowed.
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+            let claim_hash = Self::derive_key(&claim);
+
+            // Verify that the specified proof has already existed and sender of the current call is
+            // the claim owner.
+            ensure!(sender == Self::claim_info(&claim_hash)?.owner, Error::<T>::NotClaimOwner);
+
+            // Verify that there is a proposal to withdraw.
+            ensure!(PendingTransfer::<T>::contains_key(&claim_hash), Error::<T>::NoPendingTransfer);
+
+            PendingTransfer::<T>::remove(&claim_hash);
+
+            // Emit an event that the transfer was withdrawn.
+            Self::deposit_event(RawEvent::TransferCancelled(sender, claim_hash, Self::claim_seq()));
+
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Get the owner of `claim` and the block it was created in, if it exists.
+    pub fn get_claim(claim: &[u8]) -> Option<(T::AccountId, T::BlockNumber)> {
+        Self::verify_claim(claim).ok().map(|info| (info.owner, info.created_at))
+    }
+
+    /// Get the owner and creation block of each of `claims`, in the same order, or the index of
+    /// `QueryBatchTooLarge` if there are more than `MaxQueryBatch` of them.
+    pub fn get_claims(claims: Vec<Vec<u8>>) -> Result<Vec<Option<ProofOf<T>>>, u8> {
+        if claims.len() as u32 > T::MaxQueryBatch::get() {
+            return Err(Self::error_code(Error::<T>::QueryBatchTooLarge.into()));
+        }
+        Ok(claims.iter().map(|claim| Self::get_claim(claim)).collect())
+    }
+
+    /// Get the account that originally created `claim`, if it exists.
+    pub fn creator_of(claim: &[u8]) -> Option<T::AccountId> {
+        Self::verify_claim(claim).ok().map(|info| info.creator)
+    }
+
+    /// Get whether `claim` does not exist, is owned by `who` or is owned by someone else.
+    pub fn claim_status(claim: &[u8], who: &T::AccountId) -> ClaimStatus<T::AccountId> {
+        match Self::verify_claim(claim) {
+            Err(_) => ClaimStatus::NotFound,
+            Ok(info) if &info.owner == who => ClaimStatus::OwnedByCaller,
+            Ok(info) => ClaimStatus::OwnedByOther(info.owner),
+        }
+    }
+
+    /// Get the document anchored by `content_hash`, if it exists.
+    pub fn anchor(content_hash: &ClaimHash<T>) -> Option<AnchorInfo<T::AccountId, T::BlockNumber, ClaimHash<T>>> {
+        let info = Self::claim_info(content_hash).ok()?;
+        Some(AnchorInfo {
+            owner: info.owner,
+            created_at: info.created_at,
+            content_hash: *content_hash,
+            uri: UriOf::<T>::get(content_hash),
+        })
+    }
+
+    /// Derive the key that `claim` is stored under. Every call taking raw claim bytes hashes them
+    /// with this, so clients can compute the key of a document offline. If `NormalizeKeys` is set
+    /// the claim is normalized first.
+    pub fn derive_key(claim: &[u8]) -> ClaimHash<T> {
+        if T::NormalizeKeys::get() {
+            let end = claim.iter().rposition(|byte| *byte != 0).map_or(0, |last| last + 1);
+            T::Hashing::hash(&claim[..end].to_ascii_lowercase())
+        } else {
+            T::Hashing::hash(claim)
+        }
+    }
+
+    /// Get the owner of `claim`, if it exists. Other pallets can use this to check ownership
+    /// without reading `Proofs` themselves.
+    pub fn owner_of(claim: &[u8]) -> Option<T::AccountId> {
+        Self::claim_info(&Self::derive_key(claim)).ok().map(|info| info.owner)
+    }
+
+    /// Get whether `who` is an owner of `claim`, co-owners included. False if it does not exist.
+    pub fn is_owner(claim: &[u8], who: &T::AccountId) -> bool {
+        let claim_hash = Self::derive_key(claim);
+        Self::claim_info(&claim_hash).map_or(false, |info| Self::owners_of(&claim_hash, &info).contains(who))
+    }
+
+    /// Check whether `who` could create `claim` right now, without changing any state. On failure,
+    /// return the index of the error `create_claim` would fail with.
+    pub fn can_create(claim: &[u8], who: &T::AccountId) -> Result<(), u8> {
+        let check = || -> dispatch::DispatchResult {
+            ensure!(!Paused::get(), Error::<T>::Paused);
+            ensure!(claim.len() as u32 >= T::MinClaimLength::get(), Error::<T>::ClaimTooShort);
+            ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+            Self::ensure_can_create(who, who, &Self::derive_key(claim)).map(|_| ())
+        };
+        check().map_err(Self::error_code)
+    }
+
+    /// Get everything stored about `claim` that a client would show, if it exists.
+    pub fn claim_details(claim: &[u8]) -> Option<ClaimDetails<T::AccountId, T::BlockNumber>> {
+        let info = Self::verify_claim(claim).ok()?;
+        let claim_hash = Self::derive_key(claim);
+        let metadata = MetadataOf::<T>::get(&claim_hash);
+        Some(ClaimDetails {
+            owner: info.owner,
+            created_at: info.created_at,
+            expiry: ExpiryOf::<T>::get(&claim_hash),
+            metadata: if metadata.is_empty() { None } else { Some(metadata) },
+            frozen: Frozen::<T>::get(&claim_hash),
+            locked_until: LockedUntil::<T>::get(&claim_hash),
+        })
+    }
+
+    /// Get the message `owner` signs to authorize relaying `claim` with `nonce`. Like EIP-191, it
+    /// is prefixed so that it can never be mistaken for a transaction.
+    pub fn signed_claim_payload(claim: &[u8], owner: &T::AccountId, nonce: u64) -> Vec<u8> {
+        (SIGNED_CLAIM_PREFIX, claim, owner, nonce).encode()
+    }
+
+    /// Get the message `cosigner` signs to approve `owner` creating `claim` with `nonce`, prefixed
+    /// like `signed_claim_payload`.
+    pub fn cosigned_claim_payload(claim: &[u8], owner: &T::AccountId, nonce: u64) -> Vec<u8> {
+        (COSIGNED_CLAIM_PREFIX, claim, owner, nonce).encode()
+    }
+
+    /// Get the fee for creating a claim now: `FeeBase` plus `FeeSlope` for every claim on chain.
+    pub fn claim_fee() -> BalanceOf<T> {
+        let slope = T::FeeSlope::get().saturating_mul(TotalProofs::get().into());
+        T::FeeBase::get().saturating_add(slope)
+    }
+
+    /// Get the header stored with `claim`, if it exists.
+    pub fn claim_header(claim: &[u8]) -> Option<ClaimHeader> {
+        Self::verify_claim(claim).ok().map(|_| HeaderOf::<T>::get(Self::derive_key(claim)))
+    }
+
+    /// Get how many blocks before `now` `claim` was created, if it exists.
+    pub fn claim_age(claim: &[u8], now: T::BlockNumber) -> Option<T::BlockNumber> {
+        Self::verify_claim(claim).ok().map(|info| now.saturating_sub(info.created_at))
+    }
+
+    /// Get the timestamp of the block `claim` was created in, if it exists.
+    pub fn timestamp_of(claim: &[u8]) -> Option<T::Moment> {
+        Self::verify_claim(claim).ok().map(|info| info.timestamp)
+    }
+
+    /// The account of the treasury, which receives claim fees.
+    pub fn treasury_account() -> T::AccountId {
+        T::TreasuryModuleId::get().into_account()
+    }
+
+    /// Get the claim that `alias` resolves to, if any.
+    pub fn resolve_alias(alias: &[u8]) -> Option<ClaimHash<T>> {
+        AliasToClaim::<T>::get(alias)
+    }
+
+    /// The account of the escrow, which owns claims placed in escrow.
+    pub fn escrow_account() -> T::AccountId {
+        T::EscrowModuleId::get().into_account()
+    }
+
+    /// Get everything stored about `claim`, or `ClaimNotExist` if it has not been claimed.
+    pub fn verify_claim(claim: &[u8]) -> Result<ClaimInfoOf<T>, Error<T>> {
+        Self::claim_info(&Self::derive_key(claim))
+    }
+
+    /// Get the accounts `claim` was transferred to and the blocks they received it in, oldest
+    /// first.
+    pub fn history_of(claim: &[u8]) -> Vec<(T::AccountId, T::BlockNumber)> {
+        HistoryOf::<T>::get(Self::derive_key(claim))
+    }
+
+    /// Get the hashes of all claims owned by `who`.
+    pub fn claims_of(who: &T::AccountId) -> Vec<ClaimHash<T>> {
+        ClaimsByOwner::<T>::iter_prefix(who).map(|(claim_hash, ())| claim_hash).collect()
+    }
+
+    /// Get the hashes of all claims in `category`.
+    pub fn claims_in_category(category: u8) -> Vec<ClaimHash<T>> {
+        ClaimsByCategory::<T>::iter_prefix(category).map(|(claim_hash, ())| claim_hash).collect()
+    }
+
+    /// Get up to `limit` SCALE-encoded hashes of the claims owned by `who`, starting at
+    /// `start_key`, along with the key to start the next page at. The next key is `None` once
+    /// every claim has been returned.
+    ///
+    /// The walk resumes from the raw storage key of `start_key`, so a page costs `limit` reads and
+    /// carries on where it left off even if the claim at `start_key` has since been removed.
+    pub fn claims_of_paged(
+        who: &T::AccountId,
+        start_key: Option<Vec<u8>>,
+        limit: u32,
+    ) -> (Vec<Vec<u8>>, Option<Vec<u8>>) {
+        let prefix = <ClaimsByOwner<T> as storage::generator::StorageDoubleMap<_, _, _>>::storage_double_map_final_key1(who);
+        // Each raw key is the prefix, the 16-byte Blake2 hash of the claim hash and the claim hash.
+        let claim_key = |raw: &[u8]| raw[prefix.len() + 16..].to_vec();
+        let next = |raw: &[u8]| sp_io::storage::next_key(raw).filter(|next| next.starts_with(&prefix));
+
+        let mut raw = match start_key {
+            Some(start) => {
+                let mut raw = prefix.clone();
+                raw.extend(Blake2_128Concat::hash(&start));
+                if sp_io::storage::exists(&raw) { Some(raw) } else { next(&raw) }
+            },
+            None => next(&prefix),
+        };
+
+        let mut page = Vec::new();
+        while let Some(key) = raw {
+            if page.len() as u32 >= limit {
+                return (page, Some(claim_key(&key)));
+            }
+            page.push(claim_key(&key));
+            raw = next(&key);
+        }
+        (page, None)
+    }
+
+    /// Get the hashes of the claims created from block `from` to block `to`, both included, in the
+    /// order they were created, and the block to carry on from, or `None` if the range is done.
+    /// Like `CreatedInBlock`, this includes claims that have since been removed. Nothing is
+    /// returned if `from` is after `to`.
+    ///
+    /// The walk stops once it has collected `limit` claims or scanned `MAX_RANGE_SPAN` blocks.
+    /// Blocks are returned whole, so the last one may take the result past `limit`.
+    pub fn claims_in_range(
+        from: T::BlockNumber,
+        to: T::BlockNumber,
+        limit: u32,
+    ) -> (Vec<ClaimHash<T>>, Option<T::BlockNumber>) {
+        // No claims can have been created after the current block.
+        let to = to.min(frame_system::Module::<T>::block_number());
+        let mut claims = Vec::new();
+        let mut block = from;
+        let mut scanned = 0;
+        while block <= to {
+            if claims.len() as u32 >= limit || scanned >= MAX_RANGE_SPAN {
+                return (claims, Some(block));
+            }
+            claims.extend(CreatedInBlock::<T>::get(block));
+            scanned += 1;
+            if block == to {
+                break;
+            }
+            block += One::one();
+        }
+        (claims, None)
+    }
+
+    /// Get how many accounts own each number of claims: bucket `i` counts the accounts owning
+    /// `i + 1` claims, except for the last, which counts every account owning at least as many.
+    /// Accounts without claims are not counted. At most `MAX_OWNERSHIP_BUCKETS` buckets are
+    /// returned.
+    ///
+    /// This iterates over every account in `ClaimsOf`, so it is only meant for off-chain or
+    /// archival queries.
+    pub fn ownership_distribution(buckets: u32) -> Vec<u32> {
+        let buckets = buckets.min(MAX_OWNERSHIP_BUCKETS) as usize;
+        let mut distribution = vec![0; buckets];
+        if buckets == 0 {
+            return distribution;
+        }
+        for (_, count) in ClaimsOf::<T>::iter() {
+            if count > 0 {
+                let bucket = (count as usize - 1).min(buckets - 1);
+                distribution[bucket] += 1;
+            }
+        }
+        distribution
+    }
+
+    /// Check that the bookkeeping of this pallet agrees with `Proofs`: `TotalProofs` counts every
+    /// claim, every `ClaimsByOwner` entry names an existing claim of that owner and every
+    /// `ExpiryOf` entry names an existing claim.
+    ///
+    /// This iterates over all claims, so it is only meant for off-chain testing.
+    #[cfg(any(feature = "try-runtime", test))]
+    pub fn do_try_state() -> Result<(), &'static str> {
+        let total = Proofs::<T>::iter().count() as u32;
+        ensure!(TotalProofs::get() == total, "TotalProofs does not match the number of claims");
+
+        for (owner, claim_hash, ()) in ClaimsByOwner::<T>::iter() {
+            let info = Self::claim_info(&claim_hash).map_err(|_| "ClaimsByOwner names a missing claim")?;
+            ensure!(info.owner == owner, "ClaimsByOwner names a claim of another owner");
+        }
+
+        for (claim_hash, _) in ExpiryOf::<T>::iter() {
+            ensure!(Proofs::<T>::contains_key(&claim_hash), "ExpiryOf names a missing claim");
+        }
+
+        Ok(())
+    }
+
+    // Get the claim stored under `claim_hash` in a single storage read. `Proofs` returns a default
+    // `ClaimInfo` for missing keys and maps have no `try_get` here, so read the raw value instead.
+    fn claim_info(claim_hash: &ClaimHash<T>) -> Result<ClaimInfoOf<T>, Error<T>> {
+        storage::unhashed::get(&Proofs::<T>::hashed_key_for(claim_hash)).ok_or(Error::<T>::ClaimNotExist)
+    }
+
+    // Verify that `origin` is Root or the owner of the claim stored under `claim_hash`, and that
+    // the claim exists.
+    fn ensure_owner_or_root(origin: T::Origin, claim_hash: &ClaimHash<T>) -> dispatch::DispatchResult {
+        let info = Self::claim_info(claim_hash)?;
+        match origin.into() {
+            Ok(RawOrigin::Root) => Ok(()),
+            Ok(RawOrigin::Signed(who)) => {
+                // Verify that the pallet is not paused.
+                ensure!(!Paused::get(), Error::<T>::Paused);
+                ensure!(who == info.owner, Error::<T>::NotClaimOwner);
+                Ok(())
+            }
+            _ => Err(dispatch::DispatchError::BadOrigin),
+        }
+    }
+
+    // Whether `who` may act on the claims of `owner`.
+    fn is_authorized(owner: &T::AccountId, who: &T::AccountId) -> bool {
+        who == owner || Operators::<T>::get(owner, who)
+    }
+
+    // Get the owners of a claim, falling back to its sole owner for claims created before
+    // co-ownership.
+    fn owners_of(claim_hash: &ClaimHash<T>, info: &ClaimInfoOf<T>) -> Vec<T::AccountId> {
+        let owners = OwnersOf::<T>::get(claim_hash);
+        if owners.is_empty() {
+            vec![info.owner.clone()]
+        } else {
+            owners
+        }
+    }
+
+    // Fail with `ClaimFrozen` if the claim is frozen, `ClaimLocked` if it is locked until a later
+    // block, or `ClaimChallenged` if it has a challenge that can still be resolved, whose outcome
+    // must not be dodged by moving or removing the claim and its deposit.
+    fn ensure_movable(claim_hash: &ClaimHash<T>) -> dispatch::DispatchResult {
+        ensure!(!Frozen::<T>::get(claim_hash), Error::<T>::ClaimFrozen);
+        let now = frame_system::Module::<T>::block_number();
+        if let Some(until) = LockedUntil::<T>::get(claim_hash) {
+            ensure!(now >= until, Error::<T>::ClaimLocked);
+        }
+        if let Some((_, end, _)) = Challenges::<T>::get(claim_hash) {
+            ensure!(now > end, Error::<T>::ClaimChallenged);
+        }
+        Ok(())
+    }
+
+    // Whether the claim stored under `claim_hash` is sealed and `SealBlocksRevoke` is set.
+    fn is_sealed_against_revoke(claim_hash: &ClaimHash<T>) -> bool {
+        T::SealBlocksRevoke::get() && Sealed::<T>::get(claim_hash)
+    }
+
+    // Create a claim owned by `sender`, optionally expiring at block `expiry`.
+    fn do_create_claim(
+        sender: T::AccountId,
+        claim: Vec<u8>,
+        expiry: Option<T::BlockNumber>,
+    ) -> dispatch::DispatchResult {
+        Self::do_create_claim_for(sender.clone(), sender, claim, expiry)
+    }
+
+    // Create a claim owned by `owner` and paid for by `sender`, optionally expiring at block
+    // `expiry`.
+    fn do_create_claim_for(
+        sender: T::AccountId,
+        owner: T::AccountId,
+        claim: Vec<u8>,
+        expiry: Option<T::BlockNumber>,
+    ) -> dispatch::DispatchResult {
+        // Verify that the claim is neither shorter nor longer than allowed.
+        ensure!(claim.len() as u32 >= T::MinClaimLength::get(), Error::<T>::ClaimTooShort);
+        ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+        let claim_hash = Self::derive_key(&claim);
+        Self::do_create_claim_hash(sender, owner, claim_hash, claim.len() as u32, expiry)?;
+
+        // Publish the raw claim as well, if the chain keeps it in events.
+        if !T::EmitHashedEventsOnly::get() {
+            Self::deposit_event(RawEvent::ClaimBytes(claim_hash, claim, Self::claim_seq()));
+        }
+
+        Ok(())
+    }
+
+    // Create a claim of `len` bytes stored under `claim_hash`, owned by `owner` and paid for by
+    // `sender`, optionally expiring at block `expiry`.
+    fn do_create_claim_hash(
+        sender: T::AccountId,
+        owner: T::AccountId,
+        claim_hash: ClaimHash<T>,
+        len: u32,
+        expiry: Option<T::BlockNumber>,
+    ) -> dispatch::DispatchResult {
+        let created = Self::ensure_can_create(&sender, &owner, &claim_hash)?;
+
+        // Pay the fee to the treasury and reserve the deposit for the claim.
+        let now = frame_system::Module::<T>::block_number();
+        let deposit = T::ClaimDeposit::get();
+        Self::pay_for_claim(&sender, Self::claim_fee(), deposit)?;
+
+        ClaimsThisBlock::<T>::insert(&sender, (now, created + 1));
+
+        // Store the proof with the owner, creator, block number and deposit.
+        let timestamp = pallet_timestamp::Module::<T>::get();
+        let id = Self::insert_claim(&claim_hash, ClaimInfo {
+            owner: owner.clone(),
+            creator: sender.clone(),
+            created_at: now,
+            timestamp,
+            deposit,
+        });
+        if let Some(expiry) = expiry {
+            Self::set_expiry(&claim_hash, expiry);
+        }
+        if sender != owner {
+            DepositorOf::<T>::insert(&claim_hash, sender);
+        }
+
+        // Emit an event that the claim was created.
+        Self::deposit_event(RawEvent::ClaimCreated(owner, claim_hash, len, now, timestamp, id, Self::claim_seq()));
+
+        Ok(())
+    }
+
+    // Pay `fee` from `sender` to the treasury and reserve `deposit` for a new claim. Both were
+    // checked to be affordable, but should either still fail, neither happens.
+    #[transactional]
+    fn pay_for_claim(sender: &T::AccountId, fee: BalanceOf<T>, deposit: BalanceOf<T>) -> dispatch::DispatchResult {
+        T::Currency::transfer(sender, &Self::treasury_account(), fee, ExistenceRequirement::KeepAlive)
+            .and_then(|_| T::Currency::reserve(sender, deposit))
+            .map_err(|_| Error::<T>::InsufficientBalanceForDeposit.into())
+    }
+
+    // Drop the expired claim stored under `claim_hash` and create it afresh for `sender`, like any
+    // new claim. Either both happen or, if the sender may not create it, neither does.
+    #[transactional]
+    fn do_reclaim_expired(
+        sender: T::AccountId,
+        claim_hash: &ClaimHash<T>,
+        info: &ClaimInfoOf<T>,
+        len: u32,
+    ) -> dispatch::DispatchResult {
+        Self::remove_claim(claim_hash, info);
+        Self::do_create_claim_hash(sender.clone(), sender, *claim_hash, len, None)
+    }
+
+    // Move `old_claim`, owned by `sender`, and everything stored alongside it to `new_claim`,
+    // returning the keys of both. Shared by `rename_claim` and `replace_claim`.
+    fn do_rename_claim(
+        sender: &T::AccountId,
+        old_claim: &[u8],
+        new_claim: &[u8],
+    ) -> Result<(ClaimHash<T>, ClaimHash<T>), dispatch::DispatchError> {
+        // Verify that both claims are neither shorter nor longer than allowed.
+        ensure!(old_claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+        ensure!(new_claim.len() as u32 >= T::MinClaimLength::get(), Error::<T>::ClaimTooShort);
+        ensure!(new_claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+        let old_hash = &Self::derive_key(old_claim);
+        let new_hash = &Self::derive_key(new_claim);
+
+        // Verify that the old proof exists, is owned by the sender and the new one does not exist.
+        ensure!(*sender == Self::claim_info(old_hash)?.owner, Error::<T>::NotClaimOwner);
+        ensure!(!Proofs::<T>::contains_key(new_hash), Error::<T>::ProofAlreadyExist);
+
+        // Verify that the claim is not sealed, and neither frozen, locked nor challenged.
+        ensure!(!Sealed::<T>::get(old_hash), Error::<T>::ClaimSealed);
+        Self::ensure_movable(old_hash)?;
+
+        // Move the claim and everything stored alongside it to the new key, keeping `TotalProofs`
+        // right if the move crosses the cursor of a migration in progress.
+        match (Self::counted_in_total(old_hash), Self::counted_in_total(new_hash)) {
+            (true, false) => TotalProofs::mutate(|total| *total = total.saturating_sub(1)),
+            (false, true) => TotalProofs::mutate(|total| *total = total.saturating_add(1)),
+            _ => (),
+        }
+        Proofs::<T>::swap(old_hash, new_hash);
+        ExpiryOf::<T>::swap(old_hash, new_hash);
+        if let Some(expiry) = ExpiryOf::<T>::get(new_hash) {
+            Self::set_expiry(new_hash, expiry);
+        }
+        AutoRenewOf::<T>::swap(old_hash, new_hash);
+        MetadataOf::<T>::swap(old_hash, new_hash);
+        HeaderOf::<T>::swap(old_hash, new_hash);
+        UriOf::<T>::swap(old_hash, new_hash);
+        LastTransfer::<T>::swap(old_hash, new_hash);
+        PendingTransfer::<T>::swap(old_hash, new_hash);
+        HistoryOf::<T>::swap(old_hash, new_hash);
+        LockedUntil::<T>::swap(old_hash, new_hash);
+        CategoryOf::<T>::swap(old_hash, new_hash);
+        Challenges::<T>::swap(old_hash, new_hash);
+        DepositorOf::<T>::swap(old_hash, new_hash);
+        Escrowed::<T>::swap(old_hash, new_hash);
+        Frozen::<T>::swap(old_hash, new_hash);
+        OwnersOf::<T>::swap(old_hash, new_hash);
+        IdOf::<T>::swap(old_hash, new_hash);
+        if let Some(id) = IdOf::<T>::get(new_hash) {
+            ClaimById::<T>::insert(id, new_hash);
+        }
+        AliasOf::<T>::swap(old_hash, new_hash);
+        if let Some(alias) = AliasOf::<T>::get(new_hash) {
+            AliasToClaim::<T>::insert(alias, new_hash);
+        }
+        if let Some(category) = CategoryOf::<T>::get(new_hash) {
+            ClaimsByCategory::<T>::remove(category, old_hash);
+            ClaimsByCategory::<T>::insert(category, new_hash, ());
+        }
+        ClaimsByOwner::<T>::remove(sender, old_hash);
+        ClaimsByOwner::<T>::insert(sender, new_hash, ());
+
+        Ok((*old_hash, *new_hash))
+    }
+
+    // Verify that `sender` may create a claim stored under `claim_hash` for `owner`, returning how
+    // many claims the sender has created in this block so far.
+    fn ensure_can_create(
+        sender: &T::AccountId,
+        owner: &T::AccountId,
+        claim_hash: &ClaimHash<T>,
+    ) -> Result<u32, dispatch::DispatchError> {
+        // Verify that the sender may create claims in a permissioned registry.
+        ensure!(!T::RequireAllowlist::get() || Allowlist::<T>::get(sender), Error::<T>::NotAllowed);
+
+        // Verify that the sender has not created too many claims in this block already. The
+        // counter starts over in every block.
+        let now = frame_system::Module::<T>::block_number();
+        let (last_block, created) = ClaimsThisBlock::<T>::get(sender);
+        let created = if last_block == now { created } else { 0 };
+        ensure!(created < T::MaxClaimsPerBlock::get(), Error::<T>::RateLimited);
+
+        // Verify that this block can record another claim.
+        ensure!(Self::block_has_room(now), Error::<T>::BlockClaimsFull);
+
+        // Verify that the specified proof has not already existed.
+        ensure!(!Proofs::<T>::contains_key(claim_hash), Error::<T>::ProofAlreadyExist);
+
+        // Verify that the owner has room for another claim.
+        ensure!(ClaimsOf::<T>::get(owner) < T::MaxClaimsPerAccount::get(), Error::<T>::TooManyClaims);
+
+        // Verify that the chain has room for another claim.
+        ensure!(TotalProofs::get() < T::MaxTotalProofs::get(), Error::<T>::ProofLimitReached);
+
+        // Verify that the sender can pay both the fee and the deposit.
+        ensure!(
+            T::Currency::can_reserve(sender, T::ClaimDeposit::get().saturating_add(Self::claim_fee())),
+            Error::<T>::InsufficientBalanceForDeposit
+        );
+
+        Ok(created)
+    }
+
+    // Whether `CreatedInBlock` can record another claim for `block`.
+    fn block_has_room(block: T::BlockNumber) -> bool {
+        (CreatedInBlock::<T>::decode_len(block).unwrap_or(0) as u32) < T::MaxCreatedPerBlock::get()
+    }
+
+    // Get the index of `error` in this pallet, which is the reason code reported to clients.
+    fn error_code(error: dispatch::DispatchError) -> u8 {
+        match error {
+            dispatch::DispatchError::Module { error, .. } => error,
+            _ => 0,
+        }
+    }
+
+    // Create each of `claims`, returning how many were created. Either all storage changes are
+    // kept or, if an error is returned along with the index of the failing claim, none are.
+    #[transactional]
+    fn do_create_claims(
+        sender: T::AccountId,
+        claims: Vec<Vec<u8>>,
+        best_effort: bool,
+    ) -> Result<u32, (u32, dispatch::DispatchError)> {
+        claims.into_iter().enumerate().try_fold(0, |count, (index, claim)| {
+            match Self::do_create_claim(sender.clone(), claim, None) {
+                Ok(()) => Ok(count + 1),
+                Err(_) if best_effort => Ok(count),
+                Err(e) => Err((index as u32, e)),
+            }
+        })
+    }
+
+    // Create a claim with a given owner and creation block and no deposit, returning its hash.
+    fn do_force_create(
+        claim: Vec<u8>,
+        owner: T::AccountId,
+        created_at: T::BlockNumber,
+    ) -> Result<ClaimHash<T>, dispatch::DispatchError> {
+        // Verify that the claim is neither shorter nor longer than allowed.
+        ensure!(claim.len() as u32 >= T::MinClaimLength::get(), Error::<T>::ClaimTooShort);
+        ensure!(claim.len() as u32 <= T::MaxClaimLength::get(), Error::<T>::ClaimTooLong);
+
+        let claim_hash = Self::derive_key(&claim);
+
+        // Verify that the specified proof has not already existed.
+        ensure!(!Proofs::<T>::contains_key(&claim_hash), Error::<T>::ProofAlreadyExist);
+
+        // Verify that the chain has room for another claim.
+        ensure!(TotalProofs::get() < T::MaxTotalProofs::get(), Error::<T>::ProofLimitReached);
+
+        // Verify that the block the claim is recorded in has room for it.
+        ensure!(Self::block_has_room(created_at), Error::<T>::BlockClaimsFull);
+
+        Self::insert_claim(&claim_hash, ClaimInfo {
+            owner: owner.clone(),
+            creator: owner,
+            created_at,
+            timestamp: Zero::zero(),
+            deposit: Zero::zero(),
+        });
+
+        Ok(claim_hash)
+    }
+
+    // Force create each of `entries` that does not exist yet, returning how many were created.
+    // Either all storage changes are kept or, if an error is returned along with the index of the
+    // failing entry, none are.
+    #[transactional]
+    fn do_force_create_batch(
+        entries: Vec<(Vec<u8>, T::AccountId, T::BlockNumber)>,
+    ) -> Result<u32, (u32, dispatch::DispatchError)> {
+        let already_exists: dispatch::DispatchError = Error::<T>::ProofAlreadyExist.into();
+        entries.into_iter().enumerate().try_fold(0, |count, (index, (claim, owner, created_at))| {
+            match Self::do_force_create(claim, owner, created_at) {
+                Ok(_) => Ok(count + 1),
+                Err(e) if e == already_exists => Ok(count),
+                Err(e) => Err((index as u32, e)),
+            }
+        })
+    }
+
+    // Transfer each of `claims` to `dest`, returning how many were transferred. Either all storage
+    // changes are kept or, if an error is returned along with the index of the failing claim,
+    // none are.
+    #[transactional]
+    fn do_transfer_claims(
+        sender: T::AccountId,
+        claims: Vec<Vec<u8>>,
+        dest: T::AccountId,
+        best_effort: bool,
+    ) -> Result<u32, (u32, dispatch::DispatchError)> {
+        claims.into_iter().enumerate().try_fold(0, |count, (index, claim)| {
+            let result = if claim.len() as u32 <= T::MaxClaimLength::get() {
+                Self::do_transfer_claim(sender.clone(), dest.clone(), Self::derive_key(&claim))
+            } else {
+                Err(Error::<T>::ClaimTooLong.into())
+            };
+            match result {
+                Ok(()) => Ok(count + 1),
+                Err(_) if best_effort => Ok(count),
+                Err(e) => Err((index as u32, e)),
+            }
+        })
+    }
+
+    // Get the index of the first claim that appears earlier in `claims`, if any.
+    fn first_duplicate(claims: &[Vec<u8>]) -> Option<u32> {
+        let mut seen = BTreeSet::new();
+        claims.iter().position(|claim| !seen.insert(Self::derive_key(claim))).map(|index| index as u32)
+    }
+
+    // Deposit an event with the next `ClaimSeq` as its first topic, little endian and padded with
+    // zeros, so that indexers can tell which events of a reorganized chain they have seen. Claim
+    // events read the same number with `Self::claim_seq()` when they are built.
+    fn deposit_event(event: impl Into<<T as Trait>::Event>) {
+        // Events are not recorded at genesis, so they get no sequence number either.
+        if frame_system::Module::<T>::block_number().is_zero() {
+            return;
+        }
+        let seq = ClaimSeq::mutate(|seq| { let current = *seq; *seq = seq.saturating_add(1); current });
+        let topic = T::Hash::decode(&mut TrailingZeroInput::new(&seq.encode())).unwrap_or_default();
+        frame_system::Module::<T>::deposit_event_indexed(&[topic], event.into().into());
+    }
+
+    // Whether `TotalProofs` counts the claim stored under `claim_hash`. While a migration is in
+    // progress it only counts the claims behind `MigrationCursor`, and the rest once reached.
+    fn counted_in_total(claim_hash: &ClaimHash<T>) -> bool {
+        MigrationCursor::<T>::get().map_or(true, |cursor| {
+            Proofs::<T>::hashed_key_for(claim_hash) <= Proofs::<T>::hashed_key_for(cursor)
+        })
+    }
+
+    // Count the new claim stored under `claim_hash`, raising `PeakProofs` if it is a new high, and
+    // return the new total.
+    fn increment_total_proofs(claim_hash: &ClaimHash<T>) -> u32 {
+        if !Self::counted_in_total(claim_hash) {
+            return TotalProofs::get();
+        }
+        let total = TotalProofs::mutate(|total| { *total += 1; *total });
+        PeakProofs::mutate(|peak| *peak = (*peak).max(total));
+        total
+    }
+
+    // Store a new claim and index it under its owner, returning the id assigned to it.
+    fn insert_claim(claim_hash: &ClaimHash<T>, info: ClaimInfoOf<T>) -> ClaimId {
+        let id = NextClaimId::mutate(|next| { let id = *next; *next = next.saturating_add(1); id });
+        ClaimById::<T>::insert(id, claim_hash);
+        IdOf::<T>::insert(claim_hash, id);
+        ClaimsOf::<T>::mutate(&info.owner, |count| *count += 1);
+        ClaimsByOwner::<T>::insert(&info.owner, claim_hash, ());
+        OwnersOf::<T>::insert(claim_hash, vec![info.owner.clone()]);
+        let created_at = info.created_at;
+        let total = Self::increment_total_proofs(claim_hash);
+        Proofs::<T>::insert(claim_hash, info);
+        BlockActivity::mutate(|(created, _, _)| *created = created.saturating_add(1));
+        CreatedInBlock::<T>::append(created_at, *claim_hash);
+
+        // Signal once when the store comes close to capacity, and again only after it has dropped
+        // back below the threshold.
+        let max = T::MaxTotalProofs::get();
+        if total >= max.saturating_sub(T::NearCapacityThreshold::get()) {
+            if !NearCapacity::get() {
+                NearCapacity::put(true);
+                Self::deposit_event(RawEvent::ProofStoreNearCapacity(total, max));
+            }
+        } else if NearCapacity::get() {
+            NearCapacity::kill();
+        }
+
+        id
+    }
+
+    // Revoke the claim stored under `claim_hash` on behalf of `sender`.
+    fn do_revoke_claim(sender: T::AccountId, claim_hash: ClaimHash<T>) -> dispatch::DispatchResult {
+        // Get the claim, verifying that the specified proof has already existed.
+        let mut info = Self::claim_info(&claim_hash)?;
+
+        // Verify that sender of the current call is an owner of the claim or an operator of the
+        // owner holding the deposit, reporting the attempt so that accounts probing others'
+        // claims can be flagged.
+        if !Self::is_authorized(&info.owner, &sender)
+            && !Self::owners_of(&claim_hash, &info).contains(&sender)
+        {
+            Self::deposit_event(RawEvent::UnauthorizedRevokeAttempt(sender, claim_hash, Self::claim_seq()));
+            return Err(Error::<T>::NotClaimOwner.into());
+        }
+
+        // Verify that the claim is neither frozen nor locked, nor sealed against revocation.
+        Self::ensure_movable(&claim_hash)?;
+        ensure!(!Self::is_sealed_against_revoke(&claim_hash), Error::<T>::ClaimSealed);
+
+        // Flag a claim revoked in the block it was created in, to help debug racing clients.
+        let now = frame_system::Module::<T>::block_number();
+        if info.created_at == now {
+            Self::deposit_event(RawEvent::SameBlockConflict(sender, claim_hash, Self::claim_seq()));
+        }
+
+        // Remove claim from storage and return the deposit, less any late revocation penalty.
+        let (refunded, penalty) = Self::take_late_revoke_penalty(&claim_hash, &mut info);
+        Self::remove_claim(&claim_hash, &info);
+
+        // Emit an event that the claim was erased.
+        Self::deposit_event(RawEvent::ClaimRevoked(info.owner, claim_hash, now, refunded, penalty, Self::claim_seq()));
+
+        Ok(())
+    }
+
+    // Pay the late revocation penalty, if the claim is past its grace period, from its deposit to
+    // the treasury, and lower the deposit in `info` to what is left to refund. Returns the amounts
+    // refunded and paid.
+    fn take_late_revoke_penalty(
+        claim_hash: &ClaimHash<T>,
+        info: &mut ClaimInfoOf<T>,
+    ) -> (BalanceOf<T>, BalanceOf<T>) {
+        let now = frame_system::Module::<T>::block_number();
+        let mut penalty = Zero::zero();
+        if now.saturating_sub(info.created_at) > T::GracePeriod::get() {
+            let depositor = DepositorOf::<T>::get(claim_hash).unwrap_or_else(|| info.owner.clone());
+            let due = T::LateRevokePenalty::get() * info.deposit;
+            let unmoved = T::Currency::repatriate_reserved(&depositor, &Self::treasury_account(), due, BalanceStatus::Free)
+                .unwrap_or(due);
+            penalty = due.saturating_sub(unmoved);
+            info.deposit = info.deposit.saturating_sub(penalty);
+        }
+        (info.deposit, penalty)
+    }
+
+    // Transfer the claim stored under `claim_hash` to `dest` on behalf of `sender`.
+    fn do_transfer_claim(
+        sender: T::AccountId,
+        dest: T::AccountId,
+        claim_hash: ClaimHash<T>,
+    ) -> dispatch::DispatchResult {
+        // Get the claim, verifying that the specified proof has already existed.
+        let info = Self::claim_info(&claim_hash)?;
+
+        // Verify that sender of the current call is the claim owner or one of their operators.
+        ensure!(Self::is_authorized(&info.owner, &sender), Error::<T>::NotAuthorized);
+
+        // Verify that the claim is actually changing hands, and not into escrow.
+        ensure!(dest != info.owner, Error::<T>::CannotTransferToSelf);
+        ensure!(dest != Self::escrow_account(), Error::<T>::CannotTransferToEscrow);
+
+        Self::do_transfer(&claim_hash, info, dest)
+    }
+
+    // Move a claim and its deposit from its current owner to `dest`.
+    fn do_transfer(
+        claim_hash: &ClaimHash<T>,
+        info: ClaimInfoOf<T>,
+        dest: T::AccountId,
+    ) -> dispatch::DispatchResult {
+        let sender = info.owner.clone();
+        let creator = info.creator.clone();
+
+        // Verify that the claim is neither frozen, locked nor sealed.
+        Self::ensure_movable(claim_hash)?;
+        ensure!(!Sealed::<T>::get(claim_hash), Error::<T>::ClaimSealed);
+
+        // Verify that the claim is old enough to change hands.
+        let now = frame_system::Module::<T>::block_number();
+        ensure!(now.saturating_sub(info.created_at) >= T::MinClaimAge::get(), Error::<T>::ClaimTooYoung);
+
+        // Verify that the recipient has room for another claim. The escrow account is exempt.
+        let escrow = Self::escrow_account();
+        let dest_count = ClaimsOf::<T>::get(&dest);
+        ensure!(dest == escrow || dest_count < T::MaxClaimsPerAccount::get(), Error::<T>::TooManyClaims);
+
+        // Move the deposit from the sender to the recipient. The escrow account has no funds of its
+        // own, so a claim entering escrow leaves its deposit with whoever holds it.
+        let new_deposit = if dest == escrow {
+            let depositor = DepositorOf::<T>::get(claim_hash).unwrap_or_else(|| sender.clone());
+            DepositorOf::<T>::insert(claim_hash, depositor);
+            info.deposit
+        } else {
+            let new_deposit = T::ClaimDeposit::get();
+            if T::Currency::reserve(&dest, new_deposit).is_err() {
+                // Nothing was changed yet, so record the failure for auditors and stop here.
+                let error: dispatch::DispatchError = Error::<T>::InsufficientBalanceForDeposit.into();
+                let code = Self::error_code(error);
+                Self::deposit_event(RawEvent::ClaimTransferFailed(sender, dest, *claim_hash, code, Self::claim_seq()));
+                return Err(error);
+            }
+            let depositor = DepositorOf::<T>::take(claim_hash).unwrap_or_else(|| sender.clone());
+            T::Currency::unreserve(&depositor, info.deposit);
+            new_deposit
+        };
+        let previous = LastTransfer::<T>::get(claim_hash).unwrap_or(info.created_at);
+
+        // Change the owner of the claim. Any proposed transfer is void and any co-owners are dropped
+        // once the claim moves.
+        Proofs::<T>::insert(claim_hash, ClaimInfo { owner: dest.clone(), deposit: new_deposit, ..info });
+        PendingTransfer::<T>::remove(claim_hash);
+        OwnersOf::<T>::insert(claim_hash, vec![dest.clone()]);
+        Escrowed::<T>::remove(claim_hash);
+        ClaimsByOwner::<T>::remove(&sender, claim_hash);
+        ClaimsByOwner::<T>::insert(&dest, claim_hash, ());
+        ClaimsOf::<T>::mutate(&sender, |count| *count = count.saturating_sub(1));
+        ClaimsOf::<T>::insert(&dest, dest_count + 1);
+        LastTransfer::<T>::insert(claim_hash, now);
+        BlockActivity::mutate(|(_, _, transferred)| *transferred = transferred.saturating_add(1));
+
+        // Record the transfer, dropping the oldest entries once the history is full.
+        HistoryOf::<T>::mutate(claim_hash, |history| {
+            history.push((dest.clone(), now));
+            let excess = history.len().saturating_sub(T::MaxHistoryLength::get() as usize);
+            history.drain(..excess);
+        });
+
+        // Emit an event that the claim was changed.
+        Self::deposit_event(RawEvent::ClaimTransfered(sender, dest, *claim_hash, creator, previous, now, Self::claim_seq()));
+
+        Ok(())
+    }
+
+    // Remove a claim and everything stored alongside it, returning the deposit to its owner.
+    fn remove_claim(claim_hash: &ClaimHash<T>, info: &ClaimInfoOf<T>) {
+        Proofs::<T>::remove(claim_hash);
+        ExpiryOf::<T>::remove(claim_hash);
+        AutoRenewOf::<T>::remove(claim_hash);
+        MetadataOf::<T>::remove(claim_hash);
+        HeaderOf::<T>::remove(claim_hash);
+        UriOf::<T>::remove(claim_hash);
+        LastTransfer::<T>::remove(claim_hash);
+        if let Some(id) = IdOf::<T>::take(claim_hash) {
+            ClaimById::<T>::remove(id);
+        }
+        PendingTransfer::<T>::remove(claim_hash);
+        HistoryOf::<T>::remove(claim_hash);
+        LockedUntil::<T>::remove(claim_hash);
+        Frozen::<T>::remove(claim_hash);
+        Sealed::<T>::remove(claim_hash);
+        OwnersOf::<T>::remove(claim_hash);
+        Escrowed::<T>::remove(claim_hash);
+        if let Some(alias) = AliasOf::<T>::take(claim_hash) {
+            AliasToClaim::<T>::remove(alias);
+        }
+        if let Some(category) = CategoryOf::<T>::take(claim_hash) {
+            ClaimsByCategory::<T>::remove(category, claim_hash);
+        }
+        // A challenge against a removed claim is moot, so the bond is returned.
+        if let Some((challenger, _, bond)) = Challenges::<T>::take(claim_hash) {
+            T::Currency::unreserve(&challenger, bond);
+        }
+        let depositor = DepositorOf::<T>::take(claim_hash).unwrap_or_else(|| info.owner.clone());
+        T::Currency::unreserve(&depositor, info.deposit);
+        ClaimsOf::<T>::mutate(&info.owner, |count| *count = count.saturating_sub(1));
+        ClaimsByOwner::<T>::remove(&info.owner, claim_hash);
+        if Self::counted_in_total(claim_hash) {
+            TotalProofs::mutate(|total| *total = total.saturating_sub(1));
+        }
+        BlockActivity::mutate(|(_, removed, _)| *removed = removed.saturating_add(1));
+    }
+
+    // Whether the claim stored under `claim_hash` expired at or before `now` and may be removed.
+    // Frozen, locked and challenged claims stay until they can be moved again, and nothing is
+    // removed while a migration is in progress, as claims ahead of its cursor cannot be read yet.
+    fn is_prunable(claim_hash: &ClaimHash<T>, expiry: T::BlockNumber, now: T::BlockNumber) -> bool {
+        expiry <= now && !MigrationCursor::<T>::exists() && Self::ensure_movable(claim_hash).is_ok()
+    }
+
+    // Set the expiry of the claim stored under `claim_hash` and index it for pruning. A claim due
+    // before the pruning cursor is indexed at the cursor, so that it is still reached.
+    fn set_expiry(claim_hash: &ClaimHash<T>, expiry: T::BlockNumber) {
+        ExpiryOf::<T>::insert(claim_hash, expiry);
+        ExpiringAt::<T>::append(expiry.max(PruneCursor::<T>::get()), claim_hash);
+    }
+
+    // Whether the entry for `claim_hash` in `ExpiringAt` under `block` is still current, returning
+    // the expiry of the claim if so. Entries left behind by an expiry that was extended or removed
+    // are not current.
+    fn current_expiry(claim_hash: &ClaimHash<T>, block: T::BlockNumber) -> Option<T::BlockNumber> {
+        ExpiryOf::<T>::get(claim_hash).filter(|expiry| *expiry <= block)
+    }
+
+    // Collect up to `MaxExpiredPerBlock` claims that expired at or before `now` and may be removed,
+    // looking at no more than `MAX_EXPIRY_SCAN` blocks and entries of `ExpiringAt` from the pruning
+    // cursor on, along with the number looked at.
+    fn expired_claims(now: T::BlockNumber) -> (Vec<ClaimHash<T>>, u64) {
+        let mut expired = Vec::new();
+        let mut scanned: u32 = 0;
+        if MigrationCursor::<T>::exists() {
+            return (expired, 0);
+        }
+
+        let mut block = PruneCursor::<T>::get();
+        'blocks: while block <= now {
+            for claim_hash in ExpiringAt::<T>::get(block).iter().rev() {
+                if scanned >= MAX_EXPIRY_SCAN || expired.len() as u32 >= T::MaxExpiredPerBlock::get() {
+                    break 'blocks;
+                }
+                scanned += 1;
+                let prunable = Self::current_expiry(claim_hash, block)
+                    .map_or(false, |expiry| Self::is_prunable(claim_hash, expiry, now));
+                if prunable && !expired.contains(claim_hash) {
+                    expired.push(*claim_hash);
+                }
+            }
+            if scanned >= MAX_EXPIRY_SCAN {
+                break;
+            }
+            scanned += 1;
+            block += One::one();
+        }
+        (expired, scanned as u64)
+    }
+
+    // Remove an expired claim.
+    fn expire_claim(claim_hash: &ClaimHash<T>) {
+        let info = Proofs::<T>::get(claim_hash);
+        Self::remove_claim(claim_hash, &info);
+        Self::deposit_event(RawEvent::ClaimExpired(info.owner, *claim_hash, Self::claim_seq()));
+    }
+
+    // The most weight the pruning of a single block may consume, whatever the block has left.
+    fn prune_budget() -> Weight {
+        let scan = MAX_EXPIRY_SCAN as Weight;
+        T::DbWeight::get()
+            .reads_writes(3 + 5 * scan, 2 + scan)
+            .saturating_add(<T as Trait>::WeightInfo::revoke_expired(T::MaxExpiredPerBlock::get()))
+    }
+
+    /// Remove up to `MaxExpiredPerBlock` claims that expired at or before `now`, stopping before
+    /// the weight consumed would exceed `remaining_weight`, and return the weight consumed.
+    ///
+    /// Claims are found through `ExpiringAt`, walking the blocks from `PruneCursor` on and looking
+    /// at no more than `MAX_EXPIRY_SCAN` blocks and entries, so the work is bounded however many
+    /// claims expire. Frozen, locked and challenged claims are put back for the next block and
+    /// removed once they can be moved again.
+    ///
+    /// FRAME 2.0 has no `on_idle` hook, so `on_finalize` calls this with the weight the block has
+    /// left once every extrinsic has been applied, up to a fixed budget.
+    pub fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        let db = T::DbWeight::get();
+        if MigrationCursor::<T>::exists() {
+            return db.reads(1);
+        }
+
+        // Checking on the migration, the cursor and the entries put back for the next block.
+        let mut consumed = db.reads_writes(3, 2);
+        if consumed > remaining_weight {
+            return 0;
+        }
+        let visit = db.reads_writes(1, 1);
+        let check = db.reads(5);
+        let remove = <T as Trait>::WeightInfo::revoke_expired(1);
+
+        let mut block = PruneCursor::<T>::get();
+        let mut scanned: u32 = 0;
+        let mut removed: u32 = 0;
+        let mut deferred = Vec::new();
+        'blocks: while block <= now {
+            if scanned >= MAX_EXPIRY_SCAN || consumed.saturating_add(visit) > remaining_weight {
+                break;
+            }
+            scanned += 1;
+            consumed = consumed.saturating_add(visit);
+
+            let mut due = ExpiringAt::<T>::take(block);
+            while let Some(claim_hash) = due.last().copied() {
+                let full = scanned >= MAX_EXPIRY_SCAN || removed >= T::MaxExpiredPerBlock::get();
+                if full || consumed.saturating_add(check).saturating_add(remove) > remaining_weight {
+                    // Leave the rest of this block for the next one.
+                    ExpiringAt::<T>::insert(block, due);
+                    break 'blocks;
+                }
+                due.pop();
+                scanned += 1;
+                consumed = consumed.saturating_add(check);
+
+                if let Some(expiry) = Self::current_expiry(&claim_hash, block) {
+                    if Self::is_prunable(&claim_hash, expiry, now) {
+                        Self::expire_claim(&claim_hash);
+                        removed += 1;
+                        consumed = consumed.saturating_add(remove);
+                    } else {
+                        deferred.push(claim_hash);
+                    }
+                }
+            }
+            block += One::one();
+        }
+
+        PruneCursor::<T>::put(block);
+        if !deferred.is_empty() {
+            ExpiringAt::<T>::mutate(now + One::one(), |due| due.extend(deferred));
+        }
+
+        consumed
+    }
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    // Accept `revoke_expired` transactions from the off-chain worker as long as every claim they
+    // name has expired.
+    fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        let claims = match call {
+            Call::revoke_expired(claims) => claims,
+            _ => return InvalidTransaction::Call.into(),
+        };
+
+        let now = frame_system::Module::<T>::block_number();
+        let all_expired = claims.iter().all(|claim_hash| {
+            ExpiryOf::<T>::get(claim_hash).map_or(false, |expiry| Self::is_prunable(claim_hash, expiry, now))
+        });
+        if claims.is_empty() || claims.len() as u32 > T::MaxExpiredPerBlock::get() || !all_expired {
+            return InvalidTransaction::Stale.into();
+        }
+
+        ValidTransaction::with_tag_prefix("PoeOffchainWorker")
+            .priority(T::UnsignedPriority::get())
+            .and_provides(claims)
+            .longevity(5)
+            .propagate(true)
+            .build()
+    }
+}
+
+impl<T: Trait> Call<T> {
+    /// The claims this call would create, including the new claim of a rename and the claim that
+    /// `reclaim_expired` recreates. The match has no wildcard, so a new call does not compile
+    /// until it says whether it creates claims.
+    pub fn created_claims(&self) -> Vec<&[u8]> {
+        match self {
+            Call::create_claim(claim)
+            | Call::create_claim_idempotent(claim, _)
+            | Call::create_claim_with_expiry(claim, _)
+            | Call::create_claim_with_metadata(claim, _)
+            | Call::create_claim_with_header(claim, _)
+            | Call::create_claim_categorized(claim, _)
+            | Call::create_claim_for(claim, _)
+            | Call::create_claim_signed(claim, _, _)
+            | Call::create_claim_cosigned(claim, _, _)
+            | Call::force_create(claim, _, _)
+            | Call::reclaim_expired(claim) => vec![&claim[..]],
+            Call::create_claims(claims, _) => claims.iter().map(|claim| &claim[..]).collect(),
+            Call::force_create_batch(entries) => entries.iter().map(|(claim, _, _)| &claim[..]).collect(),
+            Call::rename_claim(_, new_claim) | Call::replace_claim(_, new_claim) => vec![&new_claim[..]],
+            Call::create_anchored(..)
+            | Call::create_claim_hash(..)
+            | Call::transfer_claims(..)
+            | Call::revoke_claim(..)
+            | Call::revoke_all()
+            | Call::force_revoke(..)
+            | Call::set_claim_metadata(..)
+            | Call::set_alias(..)
+            | Call::set_paused(..)
+            | Call::add_to_allowlist(..)
+            | Call::remove_from_allowlist(..)
+            | Call::transfer_claim(..)
+            | Call::transfer_claim_if_unchanged(..)
+            | Call::transfer_claim_v2(..)
+            | Call::revoke_claim_with_reason(..)
+            | Call::revoke_claim_hash(..)
+            | Call::transfer_claim_hash(..)
+            | Call::lock_claim(..)
+            | Call::extend_expiry(..)
+            | Call::set_auto_renew(..)
+            | Call::touch_claim(..)
+            | Call::challenge_claim(..)
+            | Call::revoke_if_expired(..)
+            | Call::resolve_challenge(..)
+            | Call::withdraw_challenge(..)
+            | Call::seal_claim(..)
+            | Call::freeze_claim(..)
+            | Call::unfreeze_claim(..)
+            | Call::add_owner(..)
+            | Call::remove_owner(..)
+            | Call::escrow_claim(..)
+            | Call::release_claim(..)
+            | Call::set_operator(..)
+            | Call::revoke_expired(..)
+            | Call::propose_transfer(..)
+            | Call::accept_transfer(..)
+            | Call::cancel_transfer(..)
+            | Call::__PhantomItem(..) => Vec::new(),
+        }
+    }
+}
+
+/// Rejects transactions creating a claim shorter than `MinClaimLength` or longer than
+/// `MaxClaimLength` before they enter the transaction pool. The claims checked are those of
+/// `Call::created_claims`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckClaimLength<T: Trait + Send + Sync>(PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckClaimLength<T> {
+    /// Create a new `CheckClaimLength`.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Trait + Send + Sync> Default for CheckClaimLength<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trait + Send + Sync> fmt::Debug for CheckClaimLength<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CheckClaimLength")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckClaimLength<T> where
+    <T as frame_system::Trait>::Call: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckClaimLength";
+    type AccountId = <T as frame_system::Trait>::AccountId;
+    type Call = <T as frame_system::Trait>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        _who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        let claims = match call.is_sub_type() {
+            Some(call) => call.created_claims(),
+            None => return Ok(ValidTransaction::default()),
+        };
+
+        let out_of_bounds = claims.iter().any(|claim| {
+            let len = claim.len() as u32;
+            len < T::MinClaimLength::get() || len > T::MaxClaimLength::get()
+        });
+        if out_of_bounds {
+            return InvalidTransaction::Call.into();
+        }
+
+        Ok(ValidTransaction::default())
     }
 }
@@ -0,0 +1,150 @@
+//! Storage migrations for the proof of existence pallet.
+
+use super::*;
+use frame_support::{
+    storage::unhashed, ReversibleStorageHasher, StoragePrefixedMap, StorageValue,
+};
+
+/// The `ClaimInfo` layout stored under `Releases::V2`, before claims recorded their creator.
+#[derive(Encode, Decode)]
+struct ClaimInfoV2<AccountId, BlockNumber, Balance> {
+    owner: AccountId,
+    created_at: BlockNumber,
+    deposit: Balance,
+}
+
+/// The `ClaimInfo` layout stored under `Releases::V3`, before claims recorded their timestamp.
+#[derive(Encode, Decode)]
+struct ClaimInfoV3<AccountId, BlockNumber, Balance> {
+    owner: AccountId,
+    creator: AccountId,
+    created_at: BlockNumber,
+    deposit: Balance,
+}
+
+/// Decode a claim stored in the layout of `version` and convert it to the current `ClaimInfo`.
+///
+/// Claims stored before the creator was recorded take their current owner as creator, and
+/// claims stored before the timestamp was recorded get a zero timestamp.
+fn translate<T: Trait>(version: Releases, mut value: &[u8]) -> Option<ClaimInfoOf<T>> {
+    match version {
+        Releases::V1 => <(T::AccountId, T::BlockNumber, BalanceOf<T>)>::decode(&mut value).ok().map(
+            |(owner, created_at, deposit)| ClaimInfo {
+                creator: owner.clone(),
+                owner,
+                created_at,
+                timestamp: Zero::zero(),
+                deposit,
+            }
+        ),
+        Releases::V2 => ClaimInfoV2::<T::AccountId, T::BlockNumber, BalanceOf<T>>::decode(&mut value).ok().map(
+            |ClaimInfoV2 { owner, created_at, deposit }| ClaimInfo {
+                creator: owner.clone(),
+                owner,
+                created_at,
+                timestamp: Zero::zero(),
+                deposit,
+            }
+        ),
+        Releases::V3 => ClaimInfoV3::<T::AccountId, T::BlockNumber, BalanceOf<T>>::decode(&mut value).ok().map(
+            |ClaimInfoV3 { owner, creator, created_at, deposit }| {
+                ClaimInfo { owner, creator, created_at, timestamp: Zero::zero(), deposit }
+            }
+        ),
+        Releases::V4 | Releases::V5 => None,
+    }
+}
+
+/// Whether `value` is a whole `ClaimInfo` in the current layout. The fields of every layout have a
+/// fixed size, so a claim in an earlier layout never decodes to exactly the current length.
+fn is_current<T: Trait>(mut value: &[u8]) -> bool {
+    ClaimInfoOf::<T>::decode(&mut value).is_ok() && value.is_empty()
+}
+
+/// Migrate up to `MaxMigrationsPerBlock` claims in `Proofs` from any earlier layout to the
+/// current `ClaimInfo` and count them in `TotalProofs`, carrying on after `MigrationCursor`.
+/// Claims that cannot be decoded are removed, while claims already in the current layout, such as
+/// those created since the migration started, are left alone.
+///
+/// `TotalProofs` is reset when the migration starts and then counts the claims the cursor has
+/// passed, so claims created or removed behind the cursor are counted as usual and those ahead
+/// of it once it reaches them.
+///
+/// The stored version is only raised to `Releases::V5` once every claim has been visited. Until
+/// then the last claim visited is kept in `MigrationCursor` and `on_initialize` calls this again
+/// in every block. Does nothing if the stored version is already `Releases::V5`.
+pub fn migrate_to_v5<T: Trait>() -> Weight {
+    let version = StorageVersion::get();
+    if version == Releases::V5 {
+        return T::DbWeight::get().reads(1);
+    }
+
+    // Walk the raw keys of `Proofs`, as values in an older layout cannot be read through it.
+    let prefix = Proofs::<T>::final_prefix();
+    let cursor = MigrationCursor::<T>::get();
+    if cursor.is_none() {
+        TotalProofs::kill();
+    }
+    let mut key = cursor.map_or_else(|| prefix.to_vec(), Proofs::<T>::hashed_key_for);
+    let mut visited: u64 = 0;
+    let mut counted: u32 = 0;
+    let finished = loop {
+        let next = match frame_support::sp_io::storage::next_key(&key).filter(|next| next.starts_with(&prefix)) {
+            Some(next) => next,
+            None => break true,
+        };
+        if visited >= T::MaxMigrationsPerBlock::get() as u64 {
+            break false;
+        }
+
+        if let Some(value) = unhashed::get_raw(&next) {
+            if is_current::<T>(&value) {
+                counted += 1;
+            } else {
+                match translate::<T>(version, &value) {
+                    Some(info) => {
+                        unhashed::put(&next, &info);
+                        counted += 1;
+                    },
+                    None => unhashed::kill(&next),
+                }
+            }
+        }
+        visited += 1;
+        key = next;
+    };
+
+    TotalProofs::mutate(|total| *total = total.saturating_add(counted));
+    if finished {
+        MigrationCursor::<T>::kill();
+        StorageVersion::put(Releases::V5);
+    } else {
+        let mut claim_hash = ProofsHasher::reverse(&key[prefix.len()..]);
+        let cursor = ClaimHash::<T>::decode(&mut claim_hash).ok();
+        MigrationCursor::<T>::set(cursor);
+    }
+
+    T::DbWeight::get().reads_writes(2 * visited + 3, visited + 2)
+}
+
+/// Check the state before migrating. Claims already in the current layout must satisfy the
+/// pallet's invariants; older layouts cannot be decoded yet and are checked after migrating.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Trait>() -> Result<(), &'static str> {
+    if StorageVersion::get() == Releases::V5 {
+        Module::<T>::do_try_state()?;
+    }
+    Ok(())
+}
+
+/// Check that the state is in the current layout and satisfies the pallet's invariants, unless the
+/// migration is still in progress.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Trait>() -> Result<(), &'static str> {
+    // A migration too large for the upgrade block carries on in `on_initialize`.
+    if MigrationCursor::<T>::exists() {
+        return Ok(());
+    }
+    frame_support::ensure!(StorageVersion::get() == Releases::V5, "storage was not migrated to V5");
+    Module::<T>::do_try_state()
+}
@@ -1,15 +1,34 @@
-use crate::{Module, Trait};
+use crate::{Call, Module, Trait};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, TestXt, UintAuthorityId}, ModuleId, Perbill,
 };
 use frame_system as system;
+use std::cell::RefCell;
 
 impl_outer_origin! {
 	pub enum Origin for Test {}
 }
 
+mod poe {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		poe<T>,
+	}
+}
+
+impl_outer_dispatch! {
+	pub enum OuterCall for Test where origin: Origin {
+		poe::TemplateModule,
+	}
+}
+
 // Configure a mock runtime to test the pallet.
 
 #[derive(Clone, Eq, PartialEq)]
@@ -19,12 +38,155 @@ parameter_types! {
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const MinClaimLength: u32 = 1;
+	pub const MaxClaimLength: u32 = 16;
+	pub const MaxClaimsPerAccount: u32 = 3;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const ClaimDeposit: u64 = 10;
+	pub const FeeBase: u64 = 5;
+	pub const GracePeriod: u64 = 5;
+	pub const CleanupReward: u64 = 3;
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const EscrowModuleId: ModuleId = ModuleId(*b"py/poesc");
+	pub const MaxExpiredPerBlock: u32 = 2;
+	pub const MaxMigrationsPerBlock: u32 = 2;
+	pub const MaxTotalProofs: u32 = 5;
+	pub const NearCapacityThreshold: u32 = 1;
+	pub const MaxHistoryLength: u32 = 3;
+	pub const MaxMetadataLength: u32 = 8;
+	pub const MaxClaimVersion: u16 = 2;
+	pub const MaxUriLength: u32 = 8;
+	pub const MaxReasonLength: u32 = 10;
+	pub const MaxAliasLength: u32 = 8;
+	pub const MaxRevokeAll: u32 = 2;
+	pub const MaxBatch: u32 = 3;
+	pub const MaxQueryBatch: u32 = 4;
+	pub const SealBlocksRevoke: bool = false;
+	pub const MaxOwners: u32 = 3;
+	pub const MaxExpiry: u64 = 10;
+	pub const ChallengeBond: u64 = 20;
+	pub const ChallengePeriod: u64 = 5;
+	pub const MinimumPeriod: u64 = 5;
+	pub const UnsignedPriority: u64 = 100;
+}
+
+thread_local! {
+	static MIN_CLAIM_AGE: RefCell<u64> = RefCell::new(0);
+	static MAX_CLAIMS_PER_BLOCK: RefCell<u32> = RefCell::new(u32::max_value());
+	static MAX_CREATED_PER_BLOCK: RefCell<u32> = RefCell::new(u32::max_value());
+	static REQUIRE_ALLOWLIST: RefCell<bool> = RefCell::new(false);
+	static LATE_REVOKE_PENALTY: RefCell<Perbill> = RefCell::new(Perbill::zero());
+	static EMIT_HASHED_EVENTS_ONLY: RefCell<bool> = RefCell::new(true);
+	static NORMALIZE_KEYS: RefCell<bool> = RefCell::new(false);
+	static FEE_SLOPE: RefCell<u64> = RefCell::new(0);
+}
+
+// The minimum claim age, zero unless a test raises it with `set_min_claim_age`.
+pub struct MinClaimAge;
+impl frame_support::traits::Get<u64> for MinClaimAge {
+	fn get() -> u64 {
+		MIN_CLAIM_AGE.with(|age| *age.borrow())
+	}
+}
+
+pub fn set_min_claim_age(age: u64) {
+	MIN_CLAIM_AGE.with(|v| *v.borrow_mut() = age);
+}
+
+// The maximum claims per account per block, unlimited unless a test lowers it with
+// `set_max_claims_per_block`.
+pub struct MaxClaimsPerBlock;
+impl frame_support::traits::Get<u32> for MaxClaimsPerBlock {
+	fn get() -> u32 {
+		MAX_CLAIMS_PER_BLOCK.with(|max| *max.borrow())
+	}
+}
+
+pub fn set_max_claims_per_block(max: u32) {
+	MAX_CLAIMS_PER_BLOCK.with(|v| *v.borrow_mut() = max);
+}
+
+// The maximum claims created in one block, unlimited unless a test lowers it with
+// `set_max_created_per_block`.
+pub struct MaxCreatedPerBlock;
+impl frame_support::traits::Get<u32> for MaxCreatedPerBlock {
+	fn get() -> u32 {
+		MAX_CREATED_PER_BLOCK.with(|max| *max.borrow())
+	}
+}
+
+pub fn set_max_created_per_block(max: u32) {
+	MAX_CREATED_PER_BLOCK.with(|v| *v.borrow_mut() = max);
+}
+
+// Whether claim creation is permissioned, off unless a test turns it on with
+// `set_require_allowlist`.
+pub struct RequireAllowlist;
+impl frame_support::traits::Get<bool> for RequireAllowlist {
+	fn get() -> bool {
+		REQUIRE_ALLOWLIST.with(|required| *required.borrow())
+	}
+}
+
+pub fn set_require_allowlist(required: bool) {
+	REQUIRE_ALLOWLIST.with(|v| *v.borrow_mut() = required);
+}
+
+// The part of the deposit kept when a claim is revoked late, nothing unless a test raises it with
+// `set_late_revoke_penalty`.
+pub struct LateRevokePenalty;
+impl frame_support::traits::Get<Perbill> for LateRevokePenalty {
+	fn get() -> Perbill {
+		LATE_REVOKE_PENALTY.with(|penalty| *penalty.borrow())
+	}
+}
+
+pub fn set_late_revoke_penalty(penalty: Perbill) {
+	LATE_REVOKE_PENALTY.with(|v| *v.borrow_mut() = penalty);
+}
+
+// Whether events carry only claim hashes, on unless a test turns it off with
+// `set_emit_hashed_events_only`.
+pub struct EmitHashedEventsOnly;
+impl frame_support::traits::Get<bool> for EmitHashedEventsOnly {
+	fn get() -> bool {
+		EMIT_HASHED_EVENTS_ONLY.with(|hashed| *hashed.borrow())
+	}
+}
+
+pub fn set_emit_hashed_events_only(hashed: bool) {
+	EMIT_HASHED_EVENTS_ONLY.with(|v| *v.borrow_mut() = hashed);
+}
+
+// Whether claims are normalized before hashing, off unless a test turns it on with
+// `set_normalize_keys`.
+pub struct NormalizeKeys;
+impl frame_support::traits::Get<bool> for NormalizeKeys {
+	fn get() -> bool {
+		NORMALIZE_KEYS.with(|normalize| *normalize.borrow())
+	}
+}
+
+pub fn set_normalize_keys(normalize: bool) {
+	NORMALIZE_KEYS.with(|v| *v.borrow_mut() = normalize);
+}
+
+// How much the fee grows per claim on chain, nothing unless a test raises it with `set_fee_slope`.
+pub struct FeeSlope;
+impl frame_support::traits::Get<u64> for FeeSlope {
+	fn get() -> u64 {
+		FEE_SLOPE.with(|slope| *slope.borrow())
+	}
+}
+
+pub fn set_fee_slope(slope: u64) {
+	FEE_SLOPE.with(|v| *v.borrow_mut() = slope);
 }
 
 impl system::Trait for Test {
 	type BaseCallFilter = ();
 	type Origin = Origin;
-	type Call = ();
+	type Call = OuterCall;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
@@ -32,7 +194,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -43,19 +205,110 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+pub type Extrinsic = TestXt<Call<Test>, ()>;
+
+impl<C> system::offchain::SendTransactionTypes<C> for Test where Call<Test>: From<C> {
+	type OverarchingCall = Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
 impl Trait for Test {
-	type Event = ();
+	type Event = TestEvent;
+	type MinClaimLength = MinClaimLength;
+	type MaxClaimLength = MaxClaimLength;
+	type MaxClaimsPerAccount = MaxClaimsPerAccount;
+	type MaxClaimsPerBlock = MaxClaimsPerBlock;
+	type MaxCreatedPerBlock = MaxCreatedPerBlock;
+	type EmitHashedEventsOnly = EmitHashedEventsOnly;
+	type NormalizeKeys = NormalizeKeys;
+	type RequireAllowlist = RequireAllowlist;
+	type Currency = Balances;
+	type ClaimDeposit = ClaimDeposit;
+	type FeeBase = FeeBase;
+	type FeeSlope = FeeSlope;
+	type CleanupReward = CleanupReward;
+	type GracePeriod = GracePeriod;
+	type LateRevokePenalty = LateRevokePenalty;
+	type TreasuryModuleId = TreasuryModuleId;
+	type MaxExpiredPerBlock = MaxExpiredPerBlock;
+	type MaxMigrationsPerBlock = MaxMigrationsPerBlock;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxClaimVersion = MaxClaimVersion;
+	type MaxUriLength = MaxUriLength;
+	type MaxReasonLength = MaxReasonLength;
+	type MaxAliasLength = MaxAliasLength;
+	type MaxTotalProofs = MaxTotalProofs;
+	type NearCapacityThreshold = NearCapacityThreshold;
+	type MaxHistoryLength = MaxHistoryLength;
+	type ChallengeBond = ChallengeBond;
+	type ChallengePeriod = ChallengePeriod;
+	type ForceOrigin = system::EnsureRoot<u64>;
+	type ResolveOrigin = system::EnsureRoot<u64>;
+	type EscrowModuleId = EscrowModuleId;
+	type EscrowOrigin = system::EnsureRoot<u64>;
+	type MaxExpiry = MaxExpiry;
+	type MaxRevokeAll = MaxRevokeAll;
+	type MaxBatch = MaxBatch;
+	type MaxQueryBatch = MaxQueryBatch;
+	type SealBlocksRevoke = SealBlocksRevoke;
+	type MaxOwners = MaxOwners;
+	type MinClaimAge = MinClaimAge;
+	type UnsignedPriority = UnsignedPriority;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type WeightInfo = ();
 }
 
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Timestamp = pallet_timestamp::Module<Test>;
 pub type TemplateModule = Module<Test>;
 
 // Build genesis storage according to the mock runtime.
+// Accounts 1, 2 and 3 are endowed; account 4 has no funds.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	new_test_ext_with_claims(vec![])
+}
+
+// Build genesis storage with `initial_claims` seeded into the pallet.
+pub fn new_test_ext_with_claims(initial_claims: Vec<(Vec<u8>, u64)>) -> sp_io::TestExternalities {
+	set_min_claim_age(0);
+	set_max_claims_per_block(u32::max_value());
+	set_max_created_per_block(u32::max_value());
+	set_require_allowlist(false);
+	set_late_revoke_penalty(Perbill::zero());
+	set_emit_hashed_events_only(true);
+	set_normalize_keys(false);
+	set_fee_slope(0);
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	crate::GenesisConfig::<Test> {
+		initial_claims,
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
 }
@@ -1,23 +1,4197 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
-
-// #[test]
-// fn it_works_for_default_value() {
-// 	new_test_ext().execute_with(|| {
-// 		// Dispatch a signed extrinsic.
-// 		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-// 		// Read pallet storage and assert an expected result.
-// 		assert_eq!(TemplateModule::something(), Some(42));
-// 	});
-// }
-
-// #[test]
-// fn correct_error_for_none_value() {
-// 	new_test_ext().execute_with(|| {
-// 		// Ensure the expected error is thrown when no value is present.
-// 		assert_noop!(
-// 			TemplateModule::cause_error(Origin::signed(1)),
-// 			Error::<Test>::NoneValue
-// 		);
-// 	});
-// }
+use crate::{
+	CheckClaimLength, ClaimDetails, ClaimHeader, ClaimInfo, ClaimStatus, Error, RawEvent, MAX_EXPIRY_SCAN, MAX_OWNERSHIP_BUCKETS,
+	MAX_RANGE_SPAN,
+	mock::*,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_ok, assert_noop,
+	traits::{Currency, OffchainWorker, OnFinalize, OnInitialize, OnRuntimeUpgrade},
+	unsigned::ValidateUnsigned,
+	weights::{constants::RocksDbWeight, DispatchInfo, GetDispatchInfo, Weight},
+	StorageDoubleMap, StorageMap, StorageValue,
+};
+use sp_runtime::{testing::TestSignature, DispatchError, Perbill};
+use sp_core::{
+	offchain::{testing::{TestOffchainExt, TestTransactionPoolExt}, OffchainExt, TransactionPoolExt},
+	H256,
+};
+use sp_runtime::traits::{BlakeTwo256, Hash, SignedExtension};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+fn key(claim: &[u8]) -> H256 {
+	BlakeTwo256::hash(claim)
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		TemplateModule::on_initialize(System::block_number());
+		// Give the pruning an idle block's worth of weight, as the mock block is too small for it.
+		TemplateModule::on_idle(System::block_number(), Weight::max_value());
+	}
+}
+
+fn last_event() -> TestEvent {
+	System::events().pop().expect("an event was emitted").event
+}
+
+// The sequence number of the last event of this pallet, as carried by claim events.
+fn last_seq() -> u64 {
+	TemplateModule::claim_seq() - 1
+}
+
+#[test]
+fn create_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(TemplateModule::proofs(key(&claim)), ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_already_exist() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), claim),
+			Error::<Test>::ProofAlreadyExist
+		);
+	});
+}
+
+#[test]
+fn create_claim_accepts_max_length_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MaxClaimLength::get() as usize];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(TemplateModule::proofs(key(&claim)), ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_too_long() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MaxClaimLength::get() as usize + 1];
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), claim),
+			Error::<Test>::ClaimTooLong
+		);
+	});
+}
+
+#[test]
+fn create_claim_accepts_min_length_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MinClaimLength::get() as usize];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(TemplateModule::proofs(key(&claim)), ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_empty() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), vec![]),
+			Error::<Test>::ClaimTooShort
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert!(!crate::Proofs::<Test>::contains_key(key(&claim)));
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_not_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(2), claim),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_by_non_owner_emits_event() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+
+		// Verify that the attempt is reported and still fails with the same error.
+		assert_eq!(
+			TemplateModule::revoke_claim(Origin::signed(2), claim.clone()),
+			Err(Error::<Test>::NotClaimOwner.into())
+		);
+		assert_eq!(last_event(), RawEvent::UnauthorizedRevokeAttempt(2, key(&claim), last_seq()).into());
+		assert_eq!(TemplateModule::proofs(key(&claim)).owner, 1);
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_claim_too_long() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MaxClaimLength::get() as usize + 1];
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(1), claim),
+			Error::<Test>::ClaimTooLong
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_eq!(TemplateModule::proofs(key(&claim)), ClaimInfo { owner: 2, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_not_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(3), 2, claim),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_claim_too_long() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MaxClaimLength::get() as usize + 1];
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, claim),
+			Error::<Test>::ClaimTooLong
+		);
+	});
+}
+
+#[test]
+fn claim_is_stored_under_its_hash() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert!(crate::Proofs::<Test>::contains_key(key(&claim)));
+		// The same input always maps to the same stored key.
+		assert_eq!(key(&claim), key(&[0, 1]));
+	});
+}
+
+#[test]
+fn different_claims_map_to_different_keys() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1, 0]));
+		assert_ne!(key(&[0, 1]), key(&[1, 0]));
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])), ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+		assert_eq!(TemplateModule::proofs(key(&[1, 0])), ClaimInfo { owner: 2, creator: 2, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn create_claim_failed_when_too_many_claims() {
+	new_test_ext().execute_with(|| {
+		for i in 0..MaxClaimsPerAccount::get() as u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![i]));
+		}
+		assert_eq!(TemplateModule::claim_count_of(1), MaxClaimsPerAccount::get());
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), vec![0xff]),
+			Error::<Test>::TooManyClaims
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_frees_a_slot() {
+	new_test_ext().execute_with(|| {
+		for i in 0..MaxClaimsPerAccount::get() as u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![i]));
+		}
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_eq!(TemplateModule::claim_count_of(1), MaxClaimsPerAccount::get() - 1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0xff]));
+		assert_eq!(TemplateModule::claim_count_of(1), MaxClaimsPerAccount::get());
+	});
+}
+
+#[test]
+fn transfer_claim_moves_the_claim_count() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0]));
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+		assert_eq!(TemplateModule::claim_count_of(2), 1);
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_dest_has_too_many_claims() {
+	new_test_ext().execute_with(|| {
+		for i in 0..MaxClaimsPerAccount::get() as u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![i]));
+		}
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0xff]));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0xff]),
+			Error::<Test>::TooManyClaims
+		);
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+	});
+}
+
+#[test]
+fn create_claim_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(1), ClaimDeposit::get());
+		assert_eq!(Balances::free_balance(1), 100 - ClaimDeposit::get() - FeeBase::get());
+	});
+}
+
+#[test]
+fn create_claim_failed_when_deposit_unaffordable() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(4), vec![0, 1]),
+			Error::<Test>::InsufficientBalanceForDeposit
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_unreserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		// The fee is not refunded.
+		assert_eq!(Balances::free_balance(1), 100 - FeeBase::get());
+	});
+}
+
+#[test]
+fn transfer_claim_moves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), ClaimDeposit::get());
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_dest_cannot_afford_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 4, vec![0, 1]),
+			Error::<Test>::InsufficientBalanceForDeposit
+		);
+		assert_eq!(Balances::reserved_balance(1), ClaimDeposit::get());
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])), ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn create_claim_with_expiry_records_expiry() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 5));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(6));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 2])), None);
+	});
+}
+
+#[test]
+fn expired_claims_are_pruned() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 2));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 2], 5));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 3]));
+
+		run_to_block(2);
+		assert!(crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+
+		run_to_block(3);
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), None);
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimExpired(1, key(&[0, 1]), last_seq())));
+		assert!(crate::Proofs::<Test>::contains_key(key(&[0, 2])));
+		assert!(crate::Proofs::<Test>::contains_key(key(&[0, 3])));
+		assert_eq!(TemplateModule::claim_count_of(1), 2);
+		assert_eq!(Balances::reserved_balance(1), 2 * ClaimDeposit::get());
+	});
+}
+
+#[test]
+fn pruning_is_bounded_per_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for i in 0..3 {
+			assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![i], 1));
+		}
+
+		run_to_block(2);
+		assert_eq!(TemplateModule::claim_count_of(1), 3 - MaxExpiredPerBlock::get());
+
+		run_to_block(3);
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+	});
+}
+
+#[test]
+fn revoke_claim_clears_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 5));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), None);
+	});
+}
+
+#[test]
+fn force_revoke_works_for_root() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::force_revoke(Origin::root(), vec![0, 1]));
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimForceRevoked(key(&[0, 1]), last_seq())));
+	});
+}
+
+#[test]
+fn force_revoke_settles_challenge_lock_and_freeze() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 100));
+		assert_ok!(TemplateModule::freeze_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(2), 20);
+
+		assert_ok!(TemplateModule::force_revoke(Origin::root(), vec![0, 1]));
+
+		// Verify that the challenger got the bond back and nothing is left for the claim.
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(TemplateModule::challenge_of(key(&[0, 1])).is_none());
+		assert_eq!(TemplateModule::locked_until(key(&[0, 1])), None);
+		assert!(!TemplateModule::is_frozen(key(&[0, 1])));
+		assert!(System::events().iter().any(|record| {
+			matches!(record.event, TestEvent::poe(RawEvent::ChallengeRefunded(2, hash, 20, _)) if hash == key(&[0, 1]))
+		}));
+	});
+}
+
+#[test]
+fn force_revoke_failed_when_not_root() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::force_revoke(Origin::signed(1), vec![0, 1]),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn force_revoke_failed_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::force_revoke(Origin::root(), vec![0, 1]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn get_claim_returns_none_for_missing_claim() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), None);
+	});
+}
+
+#[test]
+fn get_claim_returns_owner_and_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((1, 2)));
+	});
+}
+
+#[test]
+fn create_claims_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![1]], false));
+		assert_eq!(TemplateModule::claim_count_of(1), 2);
+		assert_eq!(TemplateModule::get_claim(&[0]), Some((1, 1)));
+		assert_eq!(TemplateModule::get_claim(&[1]), Some((1, 1)));
+		let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+		assert!(events.contains(&TestEvent::poe(RawEvent::ClaimCreated(1, key(&[0]), 1, 1, 0, 0, 0))));
+		assert!(events.contains(&TestEvent::poe(RawEvent::ClaimCreated(1, key(&[1]), 1, 1, 0, 1, 1))));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::BatchClaimsCreated(2)));
+	});
+}
+
+#[test]
+fn create_claims_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+		assert_noop!(
+			TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![1], vec![2]], false),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(TemplateModule::get_claim(&[0]), None);
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn create_claims_best_effort_skips_failures() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+		assert_ok!(TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![1], vec![2]], true));
+		assert_eq!(TemplateModule::get_claim(&[0]), Some((1, 1)));
+		assert_eq!(TemplateModule::get_claim(&[1]), Some((2, 1)));
+		assert_eq!(TemplateModule::get_claim(&[2]), Some((1, 1)));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::BatchClaimsCreated(2)));
+	});
+}
+
+#[test]
+fn create_claims_accepts_empty_batch() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claims(Origin::signed(1), vec![], false));
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::BatchClaimsCreated(0)));
+	});
+}
+
+#[test]
+fn batch_calls_reject_more_than_max_batch_claims() {
+	new_test_ext().execute_with(|| {
+		let too_many: Vec<Vec<u8>> = (0..=MaxBatch::get() as u8).map(|claim| vec![claim]).collect();
+		assert_noop!(
+			TemplateModule::create_claims(Origin::signed(1), too_many.clone(), true),
+			Error::<Test>::BatchTooLarge
+		);
+		assert_noop!(
+			TemplateModule::transfer_claims(Origin::signed(1), too_many.clone(), 2, true),
+			Error::<Test>::BatchTooLarge
+		);
+
+		// A full batch is accepted.
+		assert_ok!(TemplateModule::create_claims(Origin::signed(1), too_many[1..].to_vec(), false));
+		assert_ok!(TemplateModule::transfer_claims(Origin::signed(1), too_many[1..].to_vec(), 2, false));
+		assert_eq!(TemplateModule::claim_count_of(2), MaxBatch::get());
+	});
+}
+
+#[test]
+fn claim_weights_scale_with_claim_length() {
+	let create = |l: usize| crate::Call::<Test>::create_claim(vec![0; l]).get_dispatch_info().weight;
+	let revoke = |l: usize| crate::Call::<Test>::revoke_claim(vec![0; l]).get_dispatch_info().weight;
+	let transfer = |l: usize| crate::Call::<Test>::transfer_claim(2, vec![0; l]).get_dispatch_info().weight;
+	assert!(create(16) > create(1));
+	assert!(revoke(16) > revoke(1));
+	assert!(transfer(16) > transfer(1));
+}
+
+#[test]
+fn revoke_and_transfer_weights_count_one_proof_read() {
+	let db = RocksDbWeight::get();
+	// Without a claim the weight is the base weight plus the storage accesses.
+	let revoke = crate::Call::<Test>::revoke_claim(vec![]).get_dispatch_info().weight;
+	let transfer = crate::Call::<Test>::transfer_claim(2, vec![]).get_dispatch_info().weight;
+	assert_eq!(revoke, 45_000_000 as Weight + db.reads(14) + db.writes(27));
+	assert_eq!(transfer, 70_000_000 as Weight + db.reads(13) + db.writes(16));
+}
+
+#[test]
+fn create_claims_weight_scales_with_batch_size() {
+	let weight = |n: usize| {
+		crate::Call::<Test>::create_claims(vec![vec![0]; n], false).get_dispatch_info().weight
+	};
+	assert!(weight(2) > weight(1));
+	assert_eq!(weight(4) - weight(2), 2 * (weight(2) - weight(1)));
+}
+
+#[test]
+fn batch_weights_scale_with_batch_size() {
+	let transfer = |n: usize| {
+		crate::Call::<Test>::transfer_claims(vec![vec![0]; n], 2, false).get_dispatch_info().weight
+	};
+	let revoke_all = crate::Call::<Test>::revoke_all().get_dispatch_info().weight;
+	assert!(transfer(2) > transfer(1));
+	assert_eq!(transfer(4) - transfer(2), 2 * (transfer(2) - transfer(1)));
+
+	// Verify that `revoke_all` is charged for the most claims it may remove.
+	assert_eq!(revoke_all, <() as crate::WeightInfo>::revoke_all(MaxRevokeAll::get()));
+}
+
+#[test]
+fn transfer_claim_failed_when_transferring_to_self() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 1, vec![0, 1]),
+			Error::<Test>::CannotTransferToSelf
+		);
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])), ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 });
+	});
+}
+
+#[test]
+fn genesis_sets_latest_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+	});
+}
+
+#[test]
+fn migrate_to_v4_converts_tuples() {
+	new_test_ext().execute_with(|| {
+		// Synthesize entries in the old tuple layout.
+		crate::StorageVersion::put(crate::Releases::V1);
+		let old: (u64, u64, u64) = (1, 5, 10);
+		frame_support::storage::unhashed::put(
+			&crate::Proofs::<Test>::hashed_key_for(key(&[0, 1])),
+			&old,
+		);
+		frame_support::storage::unhashed::put(
+			&crate::Proofs::<Test>::hashed_key_for(key(&[0, 2])),
+			&(2u64, 7u64, 0u64),
+		);
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert_eq!(TemplateModule::total_proofs(), 2);
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 1])),
+			ClaimInfo { owner: 1, creator: 1, created_at: 5, timestamp: 0, deposit: 10 }
+		);
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 2])),
+			ClaimInfo { owner: 2, creator: 2, created_at: 7, timestamp: 0, deposit: 0 }
+		);
+	});
+}
+
+#[test]
+fn migrated_v1_claims_have_empty_metadata() {
+	new_test_ext().execute_with(|| {
+		// Synthesize an entry in the old tuple layout, from before metadata existed.
+		crate::StorageVersion::put(crate::Releases::V1);
+		frame_support::storage::unhashed::put(
+			&crate::Proofs::<Test>::hashed_key_for(key(&[0, 1])),
+			&(1u64, 5u64, 10u64),
+		);
+
+		TemplateModule::on_runtime_upgrade();
+
+		let info = TemplateModule::proofs(key(&[0, 1]));
+		assert_eq!((info.owner, info.created_at), (1, 5));
+		assert!(TemplateModule::metadata_of(key(&[0, 1])).is_empty());
+
+		// Verify that the owner can attach metadata to the migrated claim.
+		assert_ok!(TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], vec![7]));
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 1])), vec![7]);
+	});
+}
+
+#[test]
+fn migrate_to_v4_adds_creator_to_v2_claims() {
+	new_test_ext().execute_with(|| {
+		// A V2 `ClaimInfo` encodes exactly like the `(owner, created_at, deposit)` tuple.
+		crate::StorageVersion::put(crate::Releases::V2);
+		frame_support::storage::unhashed::put(
+			&crate::Proofs::<Test>::hashed_key_for(key(&[0, 1])),
+			&(3u64, 4u64, 10u64),
+		);
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 1])),
+			ClaimInfo { owner: 3, creator: 3, created_at: 4, timestamp: 0, deposit: 10 }
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v4_adds_timestamp_to_v3_claims() {
+	new_test_ext().execute_with(|| {
+		// A V3 `ClaimInfo` encodes exactly like the `(owner, creator, created_at, deposit)` tuple.
+		crate::StorageVersion::put(crate::Releases::V3);
+		frame_support::storage::unhashed::put(
+			&crate::Proofs::<Test>::hashed_key_for(key(&[0, 1])),
+			&(3u64, 1u64, 4u64, 10u64),
+		);
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 1])),
+			ClaimInfo { owner: 3, creator: 1, created_at: 4, timestamp: 0, deposit: 10 }
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v4_is_noop_when_up_to_date() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		TemplateModule::on_runtime_upgrade();
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 1])),
+			ClaimInfo { owner: 1, creator: 1, created_at: 0, timestamp: 0, deposit: 10 }
+		);
+	});
+}
+
+fn sorted(mut claims: Vec<H256>) -> Vec<H256> {
+	claims.sort();
+	claims
+}
+
+#[test]
+fn claims_of_tracks_create_and_revoke() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2]));
+		assert_eq!(sorted(TemplateModule::claims_of(&1)), sorted(vec![key(&[0]), key(&[1])]));
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[2])]);
+
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_eq!(TemplateModule::claims_of(&1), vec![key(&[1])]);
+	});
+}
+
+#[test]
+fn claims_of_follows_transfers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0]));
+		assert!(TemplateModule::claims_of(&1).is_empty());
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[0])]);
+	});
+}
+
+#[test]
+fn claims_of_drops_expired_and_force_revoked_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::force_revoke(Origin::root(), vec![1]));
+		run_to_block(2);
+		assert!(TemplateModule::claims_of(&1).is_empty());
+	});
+}
+
+#[test]
+fn create_claim_with_metadata_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], b"ipfs".to_vec()));
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).owner, 1);
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 1])), b"ipfs".to_vec());
+		assert_eq!(last_event(), RawEvent::ClaimMetadataSet(1, key(&[0, 1]), last_seq()).into());
+	});
+}
+
+#[test]
+fn create_claim_with_metadata_rejects_long_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], vec![0; 9]),
+			Error::<Test>::MetadataTooLong
+		);
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], vec![0; 8]));
+	});
+}
+
+#[test]
+fn set_claim_metadata_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], b"a".to_vec()));
+		assert_ok!(TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], b"b".to_vec()));
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 1])), b"b".to_vec());
+		assert_eq!(last_event(), RawEvent::ClaimMetadataSet(1, key(&[0, 1]), last_seq()).into());
+	});
+}
+
+#[test]
+fn set_claim_metadata_failed_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], b"a".to_vec()),
+			Error::<Test>::ClaimNotExist
+		);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::set_claim_metadata(Origin::signed(2), vec![0, 1], b"a".to_vec()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], vec![0; 9]),
+			Error::<Test>::MetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_clears_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], b"ipfs".to_vec()));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert!(!crate::MetadataOf::<Test>::contains_key(key(&[0, 1])));
+	});
+}
+
+#[test]
+fn propose_and_accept_transfer_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::propose_transfer(Origin::signed(1), vec![0, 1], 2));
+		assert_eq!(TemplateModule::pending_transfer(key(&[0, 1])), Some(2));
+		assert_eq!(last_event(), RawEvent::TransferProposed(1, 2, key(&[0, 1]), last_seq()).into());
+		// Ownership does not move until the recipient accepts.
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).owner, 1);
+
+		assert_ok!(TemplateModule::accept_transfer(Origin::signed(2), vec![0, 1]));
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).owner, 2);
+		assert_eq!(TemplateModule::pending_transfer(key(&[0, 1])), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(last_event(), RawEvent::ClaimTransfered(1, 2, key(&[0, 1]), 1, 1, 1, last_seq()).into());
+	});
+}
+
+#[test]
+fn propose_transfer_failed_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::propose_transfer(Origin::signed(1), vec![0, 1], 2),
+			Error::<Test>::ClaimNotExist
+		);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::propose_transfer(Origin::signed(2), vec![0, 1], 3),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			TemplateModule::propose_transfer(Origin::signed(1), vec![0, 1], 1),
+			Error::<Test>::CannotTransferToSelf
+		);
+	});
+}
+
+#[test]
+fn accept_transfer_failed_when_not_recipient() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::accept_transfer(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NoPendingTransfer
+		);
+		assert_ok!(TemplateModule::propose_transfer(Origin::signed(1), vec![0, 1], 2));
+		assert_noop!(
+			TemplateModule::accept_transfer(Origin::signed(3), vec![0, 1]),
+			Error::<Test>::NotProposedRecipient
+		);
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).owner, 1);
+	});
+}
+
+#[test]
+fn cancel_transfer_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::cancel_transfer(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::NoPendingTransfer
+		);
+		assert_ok!(TemplateModule::propose_transfer(Origin::signed(1), vec![0, 1], 2));
+		assert_noop!(
+			TemplateModule::cancel_transfer(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(TemplateModule::cancel_transfer(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::TransferCancelled(1, key(&[0, 1]), last_seq()).into());
+		assert_noop!(
+			TemplateModule::accept_transfer(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NoPendingTransfer
+		);
+	});
+}
+
+#[test]
+fn pending_transfer_is_cleared_when_claim_moves_or_is_revoked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::propose_transfer(Origin::signed(1), vec![0], 2));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 3, vec![0]));
+		assert_eq!(TemplateModule::pending_transfer(key(&[0])), None);
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::propose_transfer(Origin::signed(1), vec![1], 2));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![1]));
+		assert_eq!(TemplateModule::pending_transfer(key(&[1])), None);
+	});
+}
+
+#[test]
+fn creator_survives_transfers() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimTransfered(2, 3, key(&[0, 1]), 1, 1, 1, last_seq()).into());
+
+		assert_eq!(TemplateModule::creator_of(&[0, 1]), Some(1));
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((3, 1)));
+		assert_eq!(TemplateModule::creator_of(&[1, 0]), None);
+	});
+}
+
+#[test]
+fn reclaim_expired_works_once_expiry_has_passed() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 2));
+		assert_ok!(TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], b"a".to_vec()));
+
+		// Skip `on_idle` so the claim is expired but not yet pruned.
+		System::set_block_number(3);
+		assert_ok!(TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]));
+
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 1])),
+			ClaimInfo { owner: 2, creator: 2, created_at: 3, timestamp: 0, deposit: 10 }
+		);
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), None);
+		assert!(!crate::MetadataOf::<Test>::contains_key(key(&[0, 1])));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[0, 1])]);
+		assert_eq!(last_event(), RawEvent::ClaimReclaimed(2, key(&[0, 1]), last_seq()).into());
+
+		// Verify that the claim was created like any other: with a new id, a fee and an index entry.
+		assert_eq!(TemplateModule::id_of(key(&[0, 1])), Some(1));
+		assert_eq!(Balances::free_balance(2), 100 - 10 - FeeBase::get());
+		assert_eq!(TemplateModule::claims_in_range(3, 3, 10), (vec![key(&[0, 1])], None));
+	});
+}
+
+#[test]
+fn reclaim_expired_checks_the_allowlist() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 2));
+		set_require_allowlist(true);
+		System::set_block_number(3);
+
+		// The expired claim is left as it was.
+		assert_noop!(
+			TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NotAllowed
+		);
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((1, 1)));
+
+		assert_ok!(TemplateModule::add_to_allowlist(Origin::root(), 2));
+		assert_ok!(TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]));
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((2, 3)));
+	});
+}
+
+#[test]
+fn reclaim_expired_failed_before_expiry() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 2));
+		System::set_block_number(2);
+		assert_noop!(
+			TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimNotExpired
+		);
+
+		// Claims without an expiry can never be reclaimed.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1, 0]));
+		assert_noop!(
+			TemplateModule::reclaim_expired(Origin::signed(2), vec![1, 0]),
+			Error::<Test>::ClaimNotExpired
+		);
+	});
+}
+
+#[test]
+fn reclaim_expired_failed_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn set_operator_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::set_operator(Origin::signed(1), 2, true));
+		assert!(TemplateModule::is_operator(1, 2));
+		assert!(!TemplateModule::is_operator(2, 1));
+		assert_eq!(last_event(), RawEvent::OperatorSet(1, 2, true).into());
+
+		assert_ok!(TemplateModule::set_operator(Origin::signed(1), 2, false));
+		assert!(!TemplateModule::is_operator(1, 2));
+		assert_eq!(last_event(), RawEvent::OperatorSet(1, 2, false).into());
+	});
+}
+
+#[test]
+fn operator_can_revoke_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_operator(Origin::signed(1), 2, true));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+		// The deposit goes back to the owner, not the operator.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(last_event(), RawEvent::ClaimRevoked(1, key(&[0, 1]), 1, 10, 0, last_seq()).into());
+	});
+}
+
+#[test]
+fn operator_can_transfer_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_operator(Origin::signed(1), 2, true));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(2), 1, vec![0, 1]),
+			Error::<Test>::CannotTransferToSelf
+		);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]));
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).owner, 3);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(3), 10);
+		assert_eq!(last_event(), RawEvent::ClaimTransfered(1, 3, key(&[0, 1]), 1, 1, 1, last_seq()).into());
+	});
+}
+
+#[test]
+fn disapproved_operator_cannot_act() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_operator(Origin::signed(1), 2, true));
+		assert_ok!(TemplateModule::set_operator(Origin::signed(1), 2, false));
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn claim_events_carry_block_number_and_length() {
+	new_test_ext().execute_with(|| {
+		run_to_block(5);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1, 2]));
+		assert_eq!(
+			last_event(),
+			RawEvent::ClaimCreated(1, key(&[0, 1, 2]), 3, System::block_number(), 0, 0, last_seq()).into()
+		);
+
+		run_to_block(7);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1, 2]));
+		assert_eq!(
+			last_event(),
+			RawEvent::ClaimRevoked(1, key(&[0, 1, 2]), System::block_number(), 10, 0, last_seq()).into()
+		);
+	});
+}
+
+#[test]
+fn genesis_seeds_initial_claims() {
+	new_test_ext_with_claims(vec![(vec![0, 1], 1), (vec![0, 2], 2), (vec![0, 3], 1)]).execute_with(|| {
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((1, 0)));
+		assert_eq!(TemplateModule::get_claim(&[0, 2]), Some((2, 0)));
+		assert_eq!(TemplateModule::creator_of(&[0, 3]), Some(1));
+		assert_eq!(TemplateModule::claim_count_of(1), 2);
+		assert_eq!(sorted(TemplateModule::claims_of(&1)), sorted(vec![key(&[0, 1]), key(&[0, 3])]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// Seeded claims behave like any other claim.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+	});
+}
+
+#[test]
+fn claims_record_the_block_timestamp() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		Timestamp::set_timestamp(12_345);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).timestamp, 12_345);
+		assert_eq!(TemplateModule::timestamp_of(&[0, 1]), Some(12_345));
+		assert_eq!(TemplateModule::timestamp_of(&[1, 0]), None);
+		assert_eq!(last_event(), RawEvent::ClaimCreated(1, key(&[0, 1]), 2, 1, 12_345, 0, last_seq()).into());
+
+		// Transfers keep the original timestamp.
+		Timestamp::set_timestamp(20_000);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_eq!(TemplateModule::timestamp_of(&[0, 1]), Some(12_345));
+	});
+}
+
+#[test]
+fn pausing_blocks_claim_extrinsics() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_paused(Origin::root(), true));
+		assert!(TemplateModule::paused());
+		assert_eq!(last_event(), RawEvent::PauseSet(true).into());
+
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), vec![0, 2]),
+			Error::<Test>::Paused
+		);
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::Paused
+		);
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]),
+			Error::<Test>::Paused
+		);
+
+		assert_ok!(TemplateModule::set_paused(Origin::root(), false));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 2]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+	});
+}
+
+#[test]
+fn set_paused_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::set_paused(Origin::signed(1), true), DispatchError::BadOrigin);
+		assert!(!TemplateModule::paused());
+	});
+}
+
+#[test]
+fn total_proofs_is_capped() {
+	new_test_ext().execute_with(|| {
+		// Three accounts with room for three claims each are enough to reach the cap of five.
+		for (who, claim) in [(1, 0u8), (1, 1), (1, 2), (2, 3), (2, 4)].iter() {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(*who), vec![*claim]));
+		}
+		assert_eq!(TemplateModule::total_proofs(), MaxTotalProofs::get());
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(3), vec![5]),
+			Error::<Test>::ProofLimitReached
+		);
+
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_eq!(TemplateModule::total_proofs(), 4);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(3), vec![5]));
+		assert_eq!(TemplateModule::total_proofs(), 5);
+	});
+}
+
+#[test]
+fn total_proofs_tracks_every_removal() {
+	new_test_ext_with_claims(vec![(vec![9], 3)]).execute_with(|| {
+		assert_eq!(TemplateModule::total_proofs(), 1);
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![1], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(2), vec![2], 5));
+		assert_eq!(TemplateModule::total_proofs(), 4);
+
+		assert_ok!(TemplateModule::force_revoke(Origin::root(), vec![0]));
+		assert_eq!(TemplateModule::total_proofs(), 3);
+
+		// Pruning removes the claim that expired at block 2.
+		run_to_block(2);
+		assert_eq!(TemplateModule::total_proofs(), 2);
+
+		// Reclaiming replaces a claim without changing the total.
+		System::set_block_number(6);
+		assert_ok!(TemplateModule::reclaim_expired(Origin::signed(1), vec![2]));
+		assert_eq!(TemplateModule::total_proofs(), 2);
+	});
+}
+
+#[test]
+fn migrate_to_v5_counts_existing_claims() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+		crate::TotalProofs::kill();
+		crate::StorageVersion::put(crate::Releases::V4);
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert_eq!(TemplateModule::total_proofs(), 2);
+	});
+}
+
+#[test]
+fn history_records_transfers_in_order() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert!(TemplateModule::history_of(&[0, 1]).is_empty());
+
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		run_to_block(2);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]));
+		run_to_block(3);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(3), 1, vec![0, 1]));
+		assert_eq!(TemplateModule::history_of(&[0, 1]), vec![(2, 1), (3, 2), (1, 3)]);
+	});
+}
+
+#[test]
+fn history_evicts_oldest_entry_when_full() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		for (block, (from, to)) in [(1, 2), (2, 3), (3, 1), (1, 2)].iter().enumerate() {
+			run_to_block(block as u64 + 1);
+			assert_ok!(TemplateModule::transfer_claim(Origin::signed(*from), *to, vec![0, 1]));
+		}
+
+		// Only the last `MaxHistoryLength` transfers are kept.
+		assert_eq!(TemplateModule::history_of(&[0, 1]), vec![(3, 2), (1, 3), (2, 4)]);
+
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+		assert!(TemplateModule::history_of(&[0, 1]).is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_submits_revoke_expired() {
+	let mut t = new_test_ext();
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	t.execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![1], 3));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2]));
+
+		// Nothing has expired yet.
+		TemplateModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// Skip `on_idle` so the expired claim is still waiting to be pruned.
+		System::set_block_number(2);
+		TemplateModule::offchain_worker(2);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		assert_eq!(tx.call, crate::Call::revoke_expired(vec![key(&[0])]));
+	});
+}
+
+#[test]
+fn revoke_expired_removes_only_expired_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![1], 3));
+		System::set_block_number(2);
+
+		assert_noop!(
+			TemplateModule::revoke_expired(Origin::signed(1), vec![key(&[0])]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::revoke_expired(Origin::none(), vec![key(&[0]), key(&[1])]));
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0])));
+		assert!(crate::Proofs::<Test>::contains_key(key(&[1])));
+		assert_eq!(last_event(), RawEvent::ClaimExpired(1, key(&[0]), last_seq()).into());
+	});
+}
+
+#[test]
+fn frozen_expired_claim_is_kept_until_unfrozen() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 1));
+		assert_ok!(TemplateModule::freeze_claim(Origin::signed(1), vec![0, 1]));
+
+		// Pruning and the unsigned path skip the claim, leaving its expiry for later.
+		run_to_block(3);
+		assert_ok!(TemplateModule::revoke_expired(Origin::none(), vec![key(&[0, 1])]));
+		assert!(TemplateModule::get_claim(&[0, 1]).is_some());
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(2));
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::Local, &crate::Call::revoke_expired(vec![key(&[0, 1])])),
+			InvalidTransaction::Stale.into()
+		);
+
+		// The signed paths fail.
+		assert_noop!(
+			TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimFrozen
+		);
+		assert_noop!(
+			TemplateModule::revoke_if_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimFrozen
+		);
+
+		// Once unfrozen, the claim is pruned.
+		assert_ok!(TemplateModule::unfreeze_claim(Origin::signed(1), vec![0, 1]));
+		run_to_block(4);
+		assert!(TemplateModule::get_claim(&[0, 1]).is_none());
+	});
+}
+
+#[test]
+fn locked_expired_claim_is_kept_until_the_lock_ends() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 1));
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 5));
+
+		run_to_block(3);
+		assert!(TemplateModule::get_claim(&[0, 1]).is_some());
+		assert_noop!(
+			TemplateModule::reclaim_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimLocked
+		);
+		assert_noop!(
+			TemplateModule::revoke_if_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimLocked
+		);
+
+		run_to_block(5);
+		assert!(TemplateModule::get_claim(&[0, 1]).is_none());
+	});
+}
+
+#[test]
+fn validate_unsigned_accepts_only_expired_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![1], 3));
+		System::set_block_number(2);
+
+		let validate = |claims| TemplateModule::validate_unsigned(
+			TransactionSource::Local,
+			&crate::Call::revoke_expired(claims),
+		);
+		assert!(validate(vec![key(&[0])]).is_ok());
+		assert_eq!(validate(vec![key(&[0]), key(&[1])]), InvalidTransaction::Stale.into());
+		assert_eq!(validate(vec![]), InvalidTransaction::Stale.into());
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::Local, &crate::Call::create_claim(vec![0])),
+			InvalidTransaction::Call.into()
+		);
+	});
+}
+
+#[test]
+fn rename_claim_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		Timestamp::set_timestamp(100);
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], b"doc".to_vec()));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+
+		run_to_block(4);
+		assert_ok!(TemplateModule::rename_claim(Origin::signed(2), vec![0, 1], vec![0, 2]));
+		assert_eq!(last_event(), RawEvent::ClaimRenamed(2, key(&[0, 1]), key(&[0, 2]), last_seq()).into());
+
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 2])),
+			ClaimInfo { owner: 2, creator: 1, created_at: 1, timestamp: 100, deposit: 10 }
+		);
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 2])), b"doc".to_vec());
+		assert_eq!(TemplateModule::history_of(&[0, 2]), vec![(2, 1)]);
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[0, 2])]);
+		assert_eq!(TemplateModule::claim_count_of(2), 1);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+	});
+}
+
+#[test]
+fn rename_claim_failed_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::rename_claim(Origin::signed(1), vec![0, 1], vec![0, 2]),
+			Error::<Test>::ClaimNotExist
+		);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![0, 2]));
+		assert_noop!(
+			TemplateModule::rename_claim(Origin::signed(2), vec![0, 1], vec![0, 3]),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			TemplateModule::rename_claim(Origin::signed(1), vec![0, 1], vec![0, 2]),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_noop!(
+			TemplateModule::rename_claim(Origin::signed(1), vec![0, 1], vec![]),
+			Error::<Test>::ClaimTooShort
+		);
+	});
+}
+
+#[test]
+fn renamed_claim_keeps_its_expiry() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 2));
+		assert_ok!(TemplateModule::rename_claim(Origin::signed(1), vec![0, 1], vec![0, 2]));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), None);
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 2])), Some(3));
+		run_to_block(3);
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 2])));
+	});
+}
+
+#[test]
+fn verify_claim_returns_the_stored_info() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(
+			TemplateModule::verify_claim(&[0, 1]).ok(),
+			Some(ClaimInfo { owner: 1, creator: 1, created_at: 2, timestamp: 0, deposit: 10 })
+		);
+	});
+}
+
+#[test]
+fn verify_claim_fails_for_missing_claim() {
+	new_test_ext().execute_with(|| {
+		assert!(matches!(TemplateModule::verify_claim(&[0, 1]), Err(Error::<Test>::ClaimNotExist)));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert!(matches!(TemplateModule::verify_claim(&[0, 1]), Err(Error::<Test>::ClaimNotExist)));
+	});
+}
+
+#[test]
+fn create_claim_pays_fee_to_treasury() {
+	new_test_ext().execute_with(|| {
+		let treasury = TemplateModule::treasury_account();
+		assert_eq!(Balances::free_balance(treasury), 0);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_eq!(Balances::free_balance(treasury), FeeBase::get());
+		assert_ok!(TemplateModule::create_claims(Origin::signed(2), vec![vec![1], vec![2]], false));
+		assert_eq!(Balances::free_balance(treasury), 3 * FeeBase::get());
+		// Transfers and revocations are free.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 3, vec![0]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(3), vec![0]));
+		assert_eq!(Balances::free_balance(treasury), 3 * FeeBase::get());
+	});
+}
+
+#[test]
+fn create_claim_failed_when_fee_unaffordable() {
+	new_test_ext().execute_with(|| {
+		// Enough for the deposit but not the fee on top of it.
+		let _ = Balances::make_free_balance_be(&4, ClaimDeposit::get() + FeeBase::get() - 1);
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(4), vec![0, 1]),
+			Error::<Test>::InsufficientBalanceForDeposit
+		);
+		assert_eq!(Balances::free_balance(TemplateModule::treasury_account()), 0);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+	});
+}
+
+#[test]
+fn pay_for_claim_returns_fee_when_deposit_cannot_be_reserved() {
+	new_test_ext().execute_with(|| {
+		// Enough for the fee but not the deposit after it.
+		let _ = Balances::make_free_balance_be(&4, FeeBase::get() + 1);
+		assert_noop!(
+			TemplateModule::pay_for_claim(&4, FeeBase::get(), ClaimDeposit::get()),
+			Error::<Test>::InsufficientBalanceForDeposit
+		);
+		assert_eq!(Balances::free_balance(4), FeeBase::get() + 1);
+		assert_eq!(Balances::free_balance(TemplateModule::treasury_account()), 0);
+	});
+}
+
+#[test]
+fn force_create_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		assert_ok!(TemplateModule::force_create(Origin::root(), vec![0, 1], 4, 3));
+		assert_eq!(
+			TemplateModule::proofs(key(&[0, 1])),
+			ClaimInfo { owner: 4, creator: 4, created_at: 3, timestamp: 0, deposit: 0 }
+		);
+		assert_eq!(TemplateModule::claims_of(&4), vec![key(&[0, 1])]);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+		assert_eq!(last_event(), RawEvent::ClaimForceCreated(4, key(&[0, 1]), last_seq()).into());
+
+		// The owner can revoke it like any other claim.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(4), vec![0, 1]));
+	});
+}
+
+#[test]
+fn force_create_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::force_create(Origin::signed(1), vec![0, 1], 1, 0),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn force_create_failed_when_claim_exists() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::force_create(Origin::root(), vec![0, 1], 2, 0),
+			Error::<Test>::ProofAlreadyExist
+		);
+	});
+}
+
+#[test]
+fn revoke_all_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2]));
+
+		assert_ok!(TemplateModule::revoke_all(Origin::signed(1)));
+		assert_eq!(last_event(), RawEvent::AllClaimsRevoked(1, 2).into());
+		assert_eq!(TemplateModule::claims_of(&1), vec![]);
+		assert_eq!(TemplateModule::claim_count_of(&1), 0);
+		assert_eq!(TemplateModule::get_claim(&[0]), None);
+		assert_eq!(TemplateModule::get_claim(&[1]), None);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+
+		// Claims of other accounts are untouched.
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[2])]);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+	});
+}
+
+#[test]
+fn revoke_all_is_bounded_per_call() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for claim in 0..3u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![claim]));
+		}
+
+		assert_ok!(TemplateModule::revoke_all(Origin::signed(1)));
+		assert_eq!(last_event(), RawEvent::AllClaimsRevoked(1, 2).into());
+		assert_eq!(TemplateModule::claims_of(&1).len(), 1);
+
+		assert_ok!(TemplateModule::revoke_all(Origin::signed(1)));
+		assert_eq!(last_event(), RawEvent::AllClaimsRevoked(1, 1).into());
+		assert_eq!(TemplateModule::claims_of(&1), vec![]);
+		assert_eq!(TemplateModule::total_proofs(), 0);
+	});
+}
+
+#[test]
+fn locked_claim_cannot_be_revoked_or_transferred_until_unlocked() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 5));
+		assert_eq!(last_event(), RawEvent::ClaimLockSet(1, key(&[0, 1]), 5, last_seq()).into());
+		assert_eq!(TemplateModule::locked_until(key(&[0, 1])), Some(5));
+
+		run_to_block(4);
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::ClaimLocked
+		);
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]),
+			Error::<Test>::ClaimLocked
+		);
+
+		run_to_block(5);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+		assert_eq!(TemplateModule::locked_until(key(&[0, 1])), None);
+	});
+}
+
+#[test]
+fn lock_claim_can_be_extended_but_not_shortened() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 5));
+
+		assert_noop!(
+			TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 4),
+			Error::<Test>::CannotShortenLock
+		);
+
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 8));
+		assert_eq!(TemplateModule::locked_until(key(&[0, 1])), Some(8));
+	});
+}
+
+#[test]
+fn lock_claim_failed_when_not_claim_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::lock_claim(Origin::signed(2), vec![0, 1], 5),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn revoke_all_skips_locked_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0], 5));
+
+		assert_ok!(TemplateModule::revoke_all(Origin::signed(1)));
+		assert_eq!(last_event(), RawEvent::AllClaimsRevoked(1, 1).into());
+		assert_eq!(TemplateModule::claims_of(&1), vec![key(&[0])]);
+	});
+}
+
+#[test]
+fn check_claim_length_rejects_oversized_and_empty_claims() {
+	new_test_ext().execute_with(|| {
+		let check = CheckClaimLength::<Test>::new();
+		let info = DispatchInfo::default();
+		let validate = |call: crate::Call<Test>| check.validate(&1, &OuterCall::TemplateModule(call), &info, 0);
+
+		assert_eq!(validate(crate::Call::create_claim(vec![0; 17])), InvalidTransaction::Call.into());
+		assert_eq!(validate(crate::Call::create_claim(vec![])), InvalidTransaction::Call.into());
+		assert_eq!(
+			validate(crate::Call::create_claim_with_expiry(vec![0; 17], 5)),
+			InvalidTransaction::Call.into()
+		);
+		assert_eq!(
+			validate(crate::Call::create_claim_with_metadata(vec![], vec![])),
+			InvalidTransaction::Call.into()
+		);
+
+		// The same check runs when the transaction is included in a block.
+		let call = OuterCall::TemplateModule(crate::Call::create_claim(vec![0; 17]));
+		assert!(check.clone().pre_dispatch(&1, &call, &info, 0).is_err());
+	});
+}
+
+#[test]
+fn check_claim_length_accepts_valid_claims_and_other_calls() {
+	new_test_ext().execute_with(|| {
+		let check = CheckClaimLength::<Test>::new();
+		let info = DispatchInfo::default();
+		let validate = |call: crate::Call<Test>| check.validate(&1, &OuterCall::TemplateModule(call), &info, 0);
+
+		assert!(validate(crate::Call::create_claim(vec![0; 1])).is_ok());
+		assert!(validate(crate::Call::create_claim(vec![0; 16])).is_ok());
+		assert!(validate(crate::Call::revoke_claim(vec![0; 17])).is_ok());
+	});
+}
+
+#[test]
+fn check_claim_length_covers_every_call_that_creates_a_claim() {
+	new_test_ext().execute_with(|| {
+		let check = CheckClaimLength::<Test>::new();
+		let info = DispatchInfo::default();
+		let validate = |call: crate::Call<Test>| check.validate(&1, &OuterCall::TemplateModule(call), &info, 0);
+		let long = vec![0; 17];
+
+		let creating = vec![
+			("create_claim", crate::Call::create_claim(long.clone())),
+			("create_claim_idempotent", crate::Call::create_claim_idempotent(long.clone(), 1)),
+			("create_claim_with_expiry", crate::Call::create_claim_with_expiry(long.clone(), 5)),
+			("create_claim_with_metadata", crate::Call::create_claim_with_metadata(long.clone(), vec![])),
+			(
+				"create_claim_with_header",
+				crate::Call::create_claim_with_header(long.clone(), ClaimHeader { version: 1, kind: 1 }),
+			),
+			("create_claim_categorized", crate::Call::create_claim_categorized(long.clone(), 1)),
+			("create_claim_for", crate::Call::create_claim_for(long.clone(), 2)),
+			("create_claim_signed", crate::Call::create_claim_signed(long.clone(), 2, TestSignature(2, vec![]))),
+			(
+				"create_claim_cosigned",
+				crate::Call::create_claim_cosigned(long.clone(), 2, Some(TestSignature(2, vec![]))),
+			),
+			("create_claims", crate::Call::create_claims(vec![vec![0], long.clone()], true)),
+			("force_create", crate::Call::force_create(long.clone(), 2, 1)),
+			("force_create_batch", crate::Call::force_create_batch(vec![(vec![0], 2, 1), (long.clone(), 2, 1)])),
+			("reclaim_expired", crate::Call::reclaim_expired(long.clone())),
+			("rename_claim", crate::Call::rename_claim(vec![0], long.clone())),
+			("replace_claim", crate::Call::replace_claim(vec![0], long)),
+		];
+		for (name, call) in creating.iter() {
+			assert_eq!(validate(call.clone()), InvalidTransaction::Call.into(), "{} was not checked", name);
+		}
+
+		// Every call that creates a claim from its bytes is listed above. `create_anchored` and
+		// `create_claim_hash` take a hash, which has no length to check.
+		let listed: Vec<&str> = creating.iter().map(|(name, _)| *name).collect();
+		for function in TemplateModule::call_functions() {
+			let name = match function.name {
+				frame_support::metadata::DecodeDifferent::Encode(name) => name,
+				_ => unreachable!("call metadata is built from static names"),
+			};
+			if name.contains("create") && name != "create_anchored" && name != "create_claim_hash" {
+				assert!(listed.contains(&name), "{} is missing from the length check test", name);
+			}
+		}
+	});
+}
+
+#[test]
+fn claims_of_paged_walks_every_claim() {
+	new_test_ext().execute_with(|| {
+		for claim in 0..3u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![claim]));
+		}
+
+		let (first, cursor) = TemplateModule::claims_of_paged(&1, None, 2);
+		assert_eq!(first.len(), 2);
+		assert!(cursor.is_some());
+
+		// Asking for the same page again gives the same result.
+		assert_eq!(TemplateModule::claims_of_paged(&1, None, 2), (first.clone(), cursor.clone()));
+
+		let (second, cursor) = TemplateModule::claims_of_paged(&1, cursor, 2);
+		assert_eq!(second.len(), 1);
+		assert_eq!(cursor, None);
+
+		let mut keys: Vec<H256> = first.iter().chain(second.iter())
+			.map(|key| H256::decode(&mut &key[..]).unwrap())
+			.collect();
+		keys.sort();
+		assert_eq!(keys, sorted(vec![key(&[0]), key(&[1]), key(&[2])]));
+	});
+}
+
+#[test]
+fn claims_of_paged_carries_on_after_the_cursor_claim_is_removed() {
+	new_test_ext().execute_with(|| {
+		for claim in 0..3u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![claim]));
+		}
+
+		let (first, cursor) = TemplateModule::claims_of_paged(&1, None, 1);
+		let cursor = cursor.unwrap();
+
+		// Revoke the claim the cursor points at before asking for the next page.
+		let cursor_claim = (0..3u8).find(|claim| key(&[*claim]).encode() == cursor).unwrap();
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![cursor_claim]));
+
+		let (second, next) = TemplateModule::claims_of_paged(&1, Some(cursor), 2);
+		assert_eq!(second.len(), 1);
+		assert_eq!(next, None);
+
+		let mut keys: Vec<H256> = first.iter().chain(second.iter())
+			.map(|key| H256::decode(&mut &key[..]).unwrap())
+			.collect();
+		keys.sort();
+		assert_eq!(keys, sorted((0..3u8).filter(|claim| *claim != cursor_claim).map(|claim| key(&[claim])).collect()));
+	});
+}
+
+#[test]
+fn claims_of_paged_ends_on_an_exact_page() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+
+		let (page, cursor) = TemplateModule::claims_of_paged(&1, None, 2);
+		assert_eq!(page.len(), 2);
+		assert_eq!(cursor, None);
+
+		assert_eq!(TemplateModule::claims_of_paged(&2, None, 2), (vec![], None));
+	});
+}
+
+#[test]
+fn claim_status_distinguishes_owner() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::claim_status(&[0, 1], &1), ClaimStatus::NotFound);
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::claim_status(&[0, 1], &1), ClaimStatus::OwnedByCaller);
+		assert_eq!(TemplateModule::claim_status(&[0, 1], &2), ClaimStatus::OwnedByOther(1));
+	});
+}
+
+#[test]
+fn extend_expiry_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 3));
+		assert_ok!(TemplateModule::extend_expiry(Origin::signed(1), vec![0, 1], 5));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(9));
+		assert_eq!(last_event(), RawEvent::ClaimExpiryExtended(key(&[0, 1]), 9, last_seq()).into());
+
+		// The claim outlives its original expiry.
+		run_to_block(4);
+		assert!(TemplateModule::get_claim(&[0, 1]).is_some());
+	});
+}
+
+#[test]
+fn touch_claim_renews_expiry_when_auto_renew_is_on() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 3));
+		assert_ok!(TemplateModule::set_auto_renew(Origin::signed(1), vec![0, 1], Some(5)));
+		assert_eq!(last_event(), RawEvent::ClaimAutoRenewSet(key(&[0, 1]), Some(5), last_seq()).into());
+
+		// Each touch resets the expiry to the time to live from now.
+		System::set_block_number(3);
+		assert_ok!(TemplateModule::touch_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(8));
+		assert_eq!(last_event(), RawEvent::ClaimExpiryExtended(key(&[0, 1]), 8, last_seq()).into());
+
+		System::set_block_number(7);
+		assert_ok!(TemplateModule::touch_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(12));
+
+		// Only the owner may touch the claim.
+		assert_noop!(
+			TemplateModule::touch_claim(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn touch_claim_failed_when_auto_renew_is_off() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 3));
+		assert_noop!(
+			TemplateModule::touch_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::AutoRenewOff
+		);
+
+		// Switching auto-renew off again stops the renewals.
+		assert_ok!(TemplateModule::set_auto_renew(Origin::signed(1), vec![0, 1], Some(5)));
+		assert_ok!(TemplateModule::set_auto_renew(Origin::signed(1), vec![0, 1], None));
+		assert_noop!(
+			TemplateModule::touch_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::AutoRenewOff
+		);
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(3));
+	});
+}
+
+#[test]
+fn set_auto_renew_failed_without_expiry_or_beyond_max_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::set_auto_renew(Origin::signed(1), vec![0, 1], Some(5)),
+			Error::<Test>::NoExpiry
+		);
+
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 2], 3));
+		assert_noop!(
+			TemplateModule::set_auto_renew(Origin::signed(1), vec![0, 2], Some(11)),
+			Error::<Test>::ExpiryTooLong
+		);
+	});
+}
+
+#[test]
+fn extend_expiry_failed_beyond_max_expiry() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 3));
+
+		// The expiry may be at most `MaxExpiry` blocks from now.
+		assert_noop!(
+			TemplateModule::extend_expiry(Origin::signed(1), vec![0, 1], 8),
+			Error::<Test>::ExpiryTooLong
+		);
+		assert_ok!(TemplateModule::extend_expiry(Origin::signed(1), vec![0, 1], 7));
+		assert_eq!(TemplateModule::expiry_of(key(&[0, 1])), Some(11));
+
+		assert_noop!(
+			TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![2], 11),
+			Error::<Test>::ExpiryTooLong
+		);
+	});
+}
+
+#[test]
+fn extend_expiry_failed_when_not_owner_or_not_expiring() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::extend_expiry(Origin::signed(1), vec![0, 1], 1),
+			Error::<Test>::ClaimNotExist
+		);
+
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 3));
+		assert_noop!(
+			TemplateModule::extend_expiry(Origin::signed(2), vec![0, 1], 1),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2]));
+		assert_noop!(
+			TemplateModule::extend_expiry(Origin::signed(1), vec![2], 1),
+			Error::<Test>::NoExpiry
+		);
+	});
+}
+
+fn near_capacity_events() -> usize {
+	System::events().iter()
+		.filter(|record| matches!(record.event, TestEvent::poe(RawEvent::ProofStoreNearCapacity(..))))
+		.count()
+}
+
+#[test]
+fn near_capacity_event_fires_once_when_crossing_threshold() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for claim in 0..3u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![claim]));
+		}
+		assert_eq!(near_capacity_events(), 0);
+
+		// The fourth claim is within `NearCapacityThreshold` of `MaxTotalProofs`.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![3]));
+		assert_eq!(near_capacity_events(), 1);
+		assert!(System::events().iter().any(|record| {
+			record.event == TestEvent::poe(RawEvent::ProofStoreNearCapacity(4, 5))
+		}));
+
+		// Hovering at or above the threshold does not signal again.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![4]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![4]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![4]));
+		assert_eq!(near_capacity_events(), 1);
+	});
+}
+
+#[test]
+fn near_capacity_event_fires_again_after_dropping_below_threshold() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for claim in 0..4u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(claim as u64 % 2 + 1), vec![claim]));
+		}
+		assert_eq!(near_capacity_events(), 1);
+
+		// Drop below the threshold; the next claim below it resets the signal.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![2]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert!(!TemplateModule::near_capacity());
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2]));
+		assert_eq!(near_capacity_events(), 2);
+	});
+}
+
+#[test]
+fn hash_claims_work_end_to_end() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let claim_hash = H256::repeat_byte(7);
+
+		assert_ok!(TemplateModule::create_claim_hash(Origin::signed(1), claim_hash));
+		assert_eq!(last_event(), RawEvent::ClaimCreated(1, claim_hash, 32, 1, 0, 0, last_seq()).into());
+		assert_eq!(TemplateModule::proofs(claim_hash).owner, 1);
+		assert_eq!(TemplateModule::claims_of(&1), vec![claim_hash]);
+		assert_noop!(
+			TemplateModule::create_claim_hash(Origin::signed(2), claim_hash),
+			Error::<Test>::ProofAlreadyExist
+		);
+
+		assert_ok!(TemplateModule::transfer_claim_hash(Origin::signed(1), 2, claim_hash));
+		assert_eq!(TemplateModule::proofs(claim_hash).owner, 2);
+		assert_eq!(
+			TemplateModule::revoke_claim_hash(Origin::signed(1), claim_hash),
+			Err(Error::<Test>::NotClaimOwner.into())
+		);
+		// The failed attempt only records `UnauthorizedRevokeAttempt`.
+		assert_eq!(last_event(), RawEvent::UnauthorizedRevokeAttempt(1, claim_hash, last_seq()).into());
+		assert_eq!(TemplateModule::proofs(claim_hash).owner, 2);
+
+		assert_ok!(TemplateModule::revoke_claim_hash(Origin::signed(2), claim_hash));
+		assert_eq!(last_event(), RawEvent::ClaimRevoked(2, claim_hash, 1, 10, 0, last_seq()).into());
+		assert_eq!(TemplateModule::total_proofs(), 0);
+		assert_noop!(
+			TemplateModule::revoke_claim_hash(Origin::signed(2), claim_hash),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn hash_claims_share_keys_with_their_preimage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_hash(Origin::signed(1), key(&[0, 1])));
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+	});
+}
+
+#[test]
+fn create_claim_categorized_indexes_the_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_categorized(Origin::signed(1), vec![0], 1));
+		assert_eq!(last_event(), RawEvent::ClaimCategorized(key(&[0]), 1, last_seq()).into());
+		assert_ok!(TemplateModule::create_claim_categorized(Origin::signed(1), vec![1], 1));
+		assert_ok!(TemplateModule::create_claim_categorized(Origin::signed(2), vec![2], 2));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![3]));
+
+		assert_eq!(TemplateModule::category_of(key(&[0])), Some(1));
+		assert_eq!(TemplateModule::category_of(key(&[3])), None);
+		assert_eq!(sorted(TemplateModule::claims_in_category(1)), sorted(vec![key(&[0]), key(&[1])]));
+		assert_eq!(TemplateModule::claims_in_category(2), vec![key(&[2])]);
+		assert_eq!(TemplateModule::claims_in_category(3), vec![]);
+	});
+}
+
+#[test]
+fn category_index_follows_revoke_and_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_categorized(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim_categorized(Origin::signed(1), vec![1], 1));
+
+		// A transferred claim stays in its category.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0]));
+		assert_eq!(sorted(TemplateModule::claims_in_category(1)), sorted(vec![key(&[0]), key(&[1])]));
+
+		// A revoked claim leaves it.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0]));
+		assert_eq!(TemplateModule::claims_in_category(1), vec![key(&[1])]);
+		assert_eq!(TemplateModule::category_of(key(&[0])), None);
+
+		// A renamed claim moves within it.
+		assert_ok!(TemplateModule::rename_claim(Origin::signed(1), vec![1], vec![2]));
+		assert_eq!(TemplateModule::claims_in_category(1), vec![key(&[2])]);
+		assert_eq!(TemplateModule::category_of(key(&[2])), Some(1));
+	});
+}
+
+#[test]
+fn upheld_challenge_revokes_claim_and_refunds_bond() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimChallenged(2, key(&[0, 1]), 6, last_seq()).into());
+		assert_eq!(Balances::reserved_balance(&2), 20);
+		assert_noop!(
+			TemplateModule::challenge_claim(Origin::signed(3), vec![0, 1]),
+			Error::<Test>::AlreadyChallenged
+		);
+
+		assert_noop!(
+			TemplateModule::resolve_challenge(Origin::signed(1), vec![0, 1], true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], true));
+		assert_eq!(last_event(), RawEvent::ChallengeResolved(key(&[0, 1]), true, last_seq()).into());
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), None);
+		assert_eq!(TemplateModule::challenge_of(key(&[0, 1])), None);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::free_balance(&2), 100);
+	});
+}
+
+#[test]
+fn rejected_challenge_slashes_bond_and_keeps_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+
+		run_to_block(6);
+		assert_ok!(TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], false));
+		assert_eq!(last_event(), RawEvent::ChallengeResolved(key(&[0, 1]), false, last_seq()).into());
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((1, 1)));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::free_balance(&2), 80);
+
+		assert_noop!(
+			TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], false),
+			Error::<Test>::NoChallenge
+		);
+	});
+}
+
+#[test]
+fn challenge_cannot_be_resolved_after_window() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::withdraw_challenge(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ChallengeWindowOpen
+		);
+
+		run_to_block(7);
+		assert_noop!(
+			TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], true),
+			Error::<Test>::ChallengeWindowClosed
+		);
+		assert_noop!(
+			TemplateModule::withdraw_challenge(Origin::signed(3), vec![0, 1]),
+			Error::<Test>::NotChallenger
+		);
+
+		// The challenger takes the bond back once the window has closed.
+		assert_ok!(TemplateModule::withdraw_challenge(Origin::signed(2), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ChallengeWithdrawn(2, key(&[0, 1]), last_seq()).into());
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn challenged_claim_cannot_be_revoked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::ClaimChallenged
+		);
+
+		// Verify that an upheld challenge can still slash the deposit.
+		assert_ok!(TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], true));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::free_balance(&1), 85);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn challenged_claim_cannot_be_transferred_or_renamed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 3, vec![0, 1]),
+			Error::<Test>::ClaimChallenged
+		);
+		assert_noop!(
+			TemplateModule::rename_claim(Origin::signed(1), vec![0, 1], vec![0, 2]),
+			Error::<Test>::ClaimChallenged
+		);
+
+		// Once the challenge can no longer be resolved the claim can move again.
+		System::set_block_number(ChallengePeriod::get() + 1);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 3, vec![0, 1]));
+	});
+}
+
+#[test]
+fn block_number_overflow_is_a_clean_error() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 3));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+
+		System::set_block_number(u64::max_value() - 1);
+		assert_noop!(
+			TemplateModule::create_claim_with_expiry(Origin::signed(2), vec![2], 2),
+			Error::<Test>::ArithmeticOverflow
+		);
+		assert_noop!(
+			TemplateModule::challenge_claim(Origin::signed(2), vec![1]),
+			Error::<Test>::ArithmeticOverflow
+		);
+
+		// Push the expiry of the first claim to the last representable block, then past it.
+		assert_ok!(TemplateModule::extend_expiry(Origin::signed(1), vec![0], u64::max_value() - 3));
+		assert_eq!(TemplateModule::expiry_of(key(&[0])), Some(u64::max_value()));
+		assert_noop!(
+			TemplateModule::extend_expiry(Origin::signed(1), vec![0], 1),
+			Error::<Test>::ArithmeticOverflow
+		);
+	});
+}
+
+#[test]
+fn create_claim_for_records_owner_and_charges_signer() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_for(Origin::signed(1), vec![0, 1], 2));
+		assert_eq!(last_event(), RawEvent::ClaimCreatedFor(1, 2, key(&[0, 1]), last_seq()).into());
+
+		let info = TemplateModule::proofs(key(&[0, 1]));
+		assert_eq!(info.owner, 2);
+		assert_eq!(info.creator, 1);
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[0, 1])]);
+		assert_eq!(TemplateModule::claims_of(&1), vec![]);
+
+		// The signer pays the fee and holds the deposit.
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_eq!(Balances::free_balance(&1), 85);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+
+		// The owner controls the claim, and revoking it returns the deposit to the signer.
+		assert_eq!(
+			TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Err(Error::<Test>::NotClaimOwner.into())
+		);
+		// The failed attempt only records `UnauthorizedRevokeAttempt`.
+		assert_eq!(last_event(), RawEvent::UnauthorizedRevokeAttempt(1, key(&[0, 1]), last_seq()).into());
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::free_balance(&1), 95);
+	});
+}
+
+#[test]
+fn transferring_a_claim_created_for_another_returns_deposit_to_signer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_for(Origin::signed(1), vec![0, 1], 2));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&3), 10);
+
+		// From then on the deposit is the new owner's.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(3), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(&3), 0);
+	});
+}
+
+/// Sign `claim` on behalf of `owner` with the given nonce.
+fn sign_claim(claim: &[u8], owner: u64, nonce: u64) -> TestSignature {
+	TestSignature(owner, TemplateModule::signed_claim_payload(claim, &owner, nonce))
+}
+
+#[test]
+fn create_claim_signed_records_owner_and_charges_relayer() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let signature = sign_claim(&[0, 1], 1, 0);
+		assert_ok!(TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 1], 1, signature));
+		assert_eq!(last_event(), RawEvent::ClaimCreatedFor(2, 1, key(&[0, 1]), last_seq()).into());
+
+		let info = TemplateModule::proofs(key(&[0, 1]));
+		assert_eq!(info.owner, 1);
+		assert_eq!(info.creator, 2);
+		assert_eq!(TemplateModule::signed_claim_nonce(&1), 1);
+
+		// The relayer pays the fee and holds the deposit.
+		assert_eq!(Balances::reserved_balance(&2), 10);
+		assert_eq!(Balances::free_balance(&2), 85);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn create_claim_signed_rejects_tampered_payload() {
+	new_test_ext().execute_with(|| {
+		// The signature covers a different claim.
+		assert_noop!(
+			TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 2], 1, sign_claim(&[0, 1], 1, 0)),
+			Error::<Test>::InvalidSignature
+		);
+		// The signature is for a different owner.
+		assert_noop!(
+			TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 1], 3, sign_claim(&[0, 1], 1, 0)),
+			Error::<Test>::InvalidSignature
+		);
+		// The signature is not the owner's.
+		assert_noop!(
+			TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 1], 1, TestSignature(
+				2,
+				TemplateModule::signed_claim_payload(&[0, 1], &1, 0),
+			)),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn create_claim_signed_rejects_replayed_nonce() {
+	new_test_ext().execute_with(|| {
+		let signature = sign_claim(&[0, 1], 1, 0);
+		assert_ok!(TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 1], 1, signature.clone()));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+
+		// The claim is free again, but the signature's nonce has been used.
+		assert_noop!(
+			TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 1], 1, signature),
+			Error::<Test>::InvalidSignature
+		);
+
+		// The owner can authorize it again with their next nonce.
+		assert_ok!(TemplateModule::create_claim_signed(Origin::signed(2), vec![0, 1], 1, sign_claim(&[0, 1], 1, 1)));
+		assert_eq!(TemplateModule::signed_claim_nonce(&1), 2);
+	});
+}
+
+/// Cosign the creation of `claim` by `owner` on behalf of `cosigner` with the given nonce.
+fn cosign_claim(claim: &[u8], owner: u64, cosigner: u64, nonce: u64) -> TestSignature {
+	TestSignature(cosigner, TemplateModule::cosigned_claim_payload(claim, &owner, nonce))
+}
+
+#[test]
+fn create_claim_cosigned_records_both_owners() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let cosign_sig = cosign_claim(&[0, 1], 1, 2, 0);
+		assert_ok!(TemplateModule::create_claim_cosigned(Origin::signed(1), vec![0, 1], 2, Some(cosign_sig)));
+		assert_eq!(last_event(), RawEvent::ClaimCosigned(1, 2, key(&[0, 1]), last_seq()).into());
+
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((1, 1)));
+		assert!(TemplateModule::is_owner(&[0, 1], &1));
+		assert!(TemplateModule::is_owner(&[0, 1], &2));
+		assert_eq!(TemplateModule::signed_claim_nonce(&2), 1);
+
+		// The sender pays the deposit.
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn create_claim_cosigned_failed_without_valid_cosignature() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_claim_cosigned(Origin::signed(1), vec![0, 1], 2, None),
+			Error::<Test>::MissingCosignature
+		);
+
+		// The cosignature covers a different claim, sender or nonce, or is not the cosigner's.
+		for cosign_sig in vec![
+			cosign_claim(&[0, 2], 1, 2, 0),
+			cosign_claim(&[0, 1], 3, 2, 0),
+			cosign_claim(&[0, 1], 1, 2, 1),
+			cosign_claim(&[0, 1], 1, 3, 0),
+		] {
+			assert_noop!(
+				TemplateModule::create_claim_cosigned(Origin::signed(1), vec![0, 1], 2, Some(cosign_sig)),
+				Error::<Test>::InvalidCosignature
+			);
+		}
+
+		// The sender cannot cosign their own claim.
+		assert_noop!(
+			TemplateModule::create_claim_cosigned(Origin::signed(1), vec![0, 1], 1, Some(cosign_claim(&[0, 1], 1, 1, 0))),
+			Error::<Test>::AlreadyOwner
+		);
+	});
+}
+
+#[test]
+fn create_claim_cosigned_rejects_replayed_cosignature() {
+	new_test_ext().execute_with(|| {
+		let cosign_sig = cosign_claim(&[0, 1], 1, 2, 0);
+		assert_ok!(TemplateModule::create_claim_cosigned(Origin::signed(1), vec![0, 1], 2, Some(cosign_sig.clone())));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+
+		assert_noop!(
+			TemplateModule::create_claim_cosigned(Origin::signed(1), vec![0, 1], 2, Some(cosign_sig)),
+			Error::<Test>::InvalidCosignature
+		);
+	});
+}
+
+#[test]
+fn frozen_claim_cannot_be_revoked_or_transferred() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::freeze_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimFrozen(key(&[0, 1]), last_seq()).into());
+		assert!(TemplateModule::is_frozen(key(&[0, 1])));
+
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::ClaimFrozen
+		);
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]),
+			Error::<Test>::ClaimFrozen
+		);
+
+		assert_ok!(TemplateModule::unfreeze_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimUnfrozen(key(&[0, 1]), last_seq()).into());
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+	});
+}
+
+#[test]
+fn sealed_claim_cannot_be_transferred_or_renamed() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::seal_claim(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(TemplateModule::seal_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimSealed(key(&[0, 1]), last_seq()).into());
+
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]),
+			Error::<Test>::ClaimSealed
+		);
+		assert_noop!(
+			TemplateModule::rename_claim(Origin::signed(1), vec![0, 1], vec![0, 2]),
+			Error::<Test>::ClaimSealed
+		);
+
+		// The seal persists across blocks.
+		run_to_block(3);
+		assert!(TemplateModule::is_sealed(key(&[0, 1])));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]),
+			Error::<Test>::ClaimSealed
+		);
+
+		// Revocation is still allowed where `SealBlocksRevoke` is off, and clears the seal.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert!(!TemplateModule::is_sealed(key(&[0, 1])));
+	});
+}
+
+#[test]
+fn root_can_freeze_and_unfreeze_any_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::freeze_claim(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(TemplateModule::freeze_claim(Origin::root(), vec![0, 1]));
+		assert!(TemplateModule::is_frozen(key(&[0, 1])));
+		assert_ok!(TemplateModule::unfreeze_claim(Origin::root(), vec![0, 1]));
+		assert!(!TemplateModule::is_frozen(key(&[0, 1])));
+
+		assert_noop!(
+			TemplateModule::freeze_claim(Origin::root(), vec![2]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn create_claims_rejects_duplicates_in_batch() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_eq!(
+			TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![1], vec![0]], false).ok(),
+			None
+		);
+		assert_eq!(last_event(), RawEvent::BatchRejected(2).into());
+
+		// No claim of the batch was created.
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+		assert_eq!(TemplateModule::total_proofs(), 0);
+		assert_eq!(TemplateModule::get_claim(&[0]), None);
+		assert_eq!(TemplateModule::get_claim(&[1]), None);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert!(matches!(
+			TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![0]], false),
+			Err(DispatchError::Module { message: Some("DuplicateInBatch"), .. })
+		));
+	});
+}
+
+#[test]
+fn create_claims_reports_index_of_failing_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+		assert!(TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![1]], false).is_err());
+		assert_eq!(last_event(), RawEvent::BatchRejected(1).into());
+		assert_eq!(TemplateModule::get_claim(&[0]), None);
+	});
+}
+
+#[test]
+fn create_claims_best_effort_skips_duplicates_in_batch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claims(Origin::signed(1), vec![vec![0], vec![0]], true));
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+	});
+}
+
+#[test]
+fn try_state_holds_after_claim_operations() {
+	new_test_ext_with_claims(vec![(vec![9], 3)]).execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![1], 5));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_eq!(TemplateModule::do_try_state(), Ok(()));
+	});
+}
+
+#[test]
+fn try_state_detects_corrupted_bookkeeping() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 5));
+
+		crate::TotalProofs::put(2);
+		assert_eq!(TemplateModule::do_try_state(), Err("TotalProofs does not match the number of claims"));
+		crate::TotalProofs::put(1);
+
+		crate::ClaimsByOwner::<Test>::insert(2, key(&[0]), ());
+		assert_eq!(TemplateModule::do_try_state(), Err("ClaimsByOwner names a claim of another owner"));
+		crate::ClaimsByOwner::<Test>::remove(2, key(&[0]));
+
+		crate::ExpiryOf::<Test>::insert(key(&[1]), 5);
+		assert_eq!(TemplateModule::do_try_state(), Err("ExpiryOf names a missing claim"));
+	});
+}
+
+#[test]
+fn proofs_keys_round_trip_through_the_selected_hasher() {
+	use frame_support::{storage::generator::StorageMap as _, IterableStorageMap, ReversibleStorageHasher};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+
+		// Verify that the raw storage key reverses to the claim hash it was built from.
+		let claim_hash = key(&[0]);
+		let raw_key = crate::Proofs::<Test>::hashed_key_for(&claim_hash);
+		let prefix = crate::Proofs::<Test>::prefix_hash();
+		assert!(raw_key.starts_with(&prefix));
+		let encoded = crate::ProofsHasher::reverse(&raw_key[prefix.len()..]);
+		assert_eq!(H256::decode(&mut &encoded[..]).ok(), Some(claim_hash));
+
+		// Verify that iteration yields the original keys.
+		let keys = crate::Proofs::<Test>::iter().map(|(claim_hash, _)| claim_hash).collect();
+		assert_eq!(sorted(keys), sorted(vec![key(&[0]), key(&[1])]));
+		assert_eq!(TemplateModule::proofs(&claim_hash).owner, 1);
+	});
+}
+
+#[test]
+fn add_and_remove_owners() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(crate::OwnersOf::<Test>::get(key(&claim)), vec![1]);
+
+		// Verify that any owner can add owners, up to `MaxOwners`.
+		assert_ok!(TemplateModule::add_owner(Origin::signed(1), claim.clone(), 2));
+		assert_eq!(last_event(), RawEvent::OwnerAdded(key(&claim), 2, last_seq()).into());
+		assert_ok!(TemplateModule::add_owner(Origin::signed(2), claim.clone(), 3));
+		assert_eq!(crate::OwnersOf::<Test>::get(key(&claim)), vec![1, 2, 3]);
+		assert_noop!(
+			TemplateModule::add_owner(Origin::signed(1), claim.clone(), 4),
+			Error::<Test>::TooManyOwners
+		);
+		assert_noop!(
+			TemplateModule::add_owner(Origin::signed(1), claim.clone(), 2),
+			Error::<Test>::AlreadyOwner
+		);
+		assert_noop!(
+			TemplateModule::add_owner(Origin::signed(4), claim.clone(), 4),
+			Error::<Test>::NotClaimOwner
+		);
+
+		// Verify that any owner can remove owners, but only listed ones.
+		assert_ok!(TemplateModule::remove_owner(Origin::signed(2), claim.clone(), 3));
+		assert_eq!(last_event(), RawEvent::OwnerRemoved(key(&claim), 3, last_seq()).into());
+		assert_eq!(crate::OwnersOf::<Test>::get(key(&claim)), vec![1, 2]);
+		assert_noop!(
+			TemplateModule::remove_owner(Origin::signed(1), claim.clone(), 3),
+			Error::<Test>::NotAnOwner
+		);
+		assert_noop!(
+			TemplateModule::remove_owner(Origin::signed(3), claim.clone(), 2),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_eq!(TemplateModule::proofs(key(&claim)).owner, 1);
+	});
+}
+
+#[test]
+fn removing_deposit_holder_passes_claim_to_next_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(TemplateModule::add_owner(Origin::signed(1), claim.clone(), 2));
+
+		assert_ok!(TemplateModule::remove_owner(Origin::signed(2), claim.clone(), 1));
+		assert_eq!(TemplateModule::proofs(key(&claim)).owner, 2);
+		assert_eq!(crate::OwnersOf::<Test>::get(key(&claim)), vec![2]);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 10);
+
+		// Verify that the last owner cannot be removed.
+		assert_noop!(
+			TemplateModule::remove_owner(Origin::signed(2), claim, 2),
+			Error::<Test>::LastOwner
+		);
+	});
+}
+
+#[test]
+fn any_owner_can_revoke_a_shared_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(TemplateModule::add_owner(Origin::signed(1), claim.clone(), 2));
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(3), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+
+		// Verify that a single co-owner's signature suffices and the deposit is returned to its holder.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), claim.clone()));
+		assert!(!crate::Proofs::<Test>::contains_key(key(&claim)));
+		assert!(!crate::OwnersOf::<Test>::contains_key(key(&claim)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn transfer_drops_co_owners() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(TemplateModule::add_owner(Origin::signed(1), claim.clone(), 3));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, claim.clone()));
+
+		assert_eq!(crate::OwnersOf::<Test>::get(key(&claim)), vec![2]);
+		assert_noop!(
+			TemplateModule::revoke_claim(Origin::signed(3), claim),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn create_anchored_stores_hash_and_uri() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let content_hash = key(b"large document");
+		assert_ok!(TemplateModule::create_anchored(Origin::signed(1), content_hash, b"ipfs://x".to_vec()));
+		assert_eq!(last_event(), RawEvent::ClaimAnchored(1, content_hash, last_seq()).into());
+		assert_eq!(TemplateModule::anchor(&content_hash), Some(crate::AnchorInfo {
+			owner: 1,
+			created_at: 1,
+			content_hash,
+			uri: b"ipfs://x".to_vec(),
+		}));
+
+		// Verify that hashing the document finds the anchor.
+		assert_eq!(TemplateModule::claim_status(b"large document", &1), ClaimStatus::OwnedByCaller);
+
+		// Revoking the anchor removes the URI with it.
+		assert_ok!(TemplateModule::revoke_claim_hash(Origin::signed(1), content_hash));
+		assert_eq!(TemplateModule::anchor(&content_hash), None);
+		assert!(TemplateModule::uri_of(content_hash).is_empty());
+	});
+}
+
+#[test]
+fn create_anchored_rejects_duplicate_content_hash() {
+	new_test_ext().execute_with(|| {
+		let content_hash = key(b"large document");
+		assert_ok!(TemplateModule::create_anchored(Origin::signed(1), content_hash, b"ipfs://x".to_vec()));
+		assert_noop!(
+			TemplateModule::create_anchored(Origin::signed(2), content_hash, b"ipfs://y".to_vec()),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(TemplateModule::uri_of(content_hash), b"ipfs://x".to_vec());
+	});
+}
+
+#[test]
+fn create_anchored_enforces_uri_length() {
+	new_test_ext().execute_with(|| {
+		let content_hash = key(b"large document");
+		assert_noop!(
+			TemplateModule::create_anchored(Origin::signed(1), content_hash, vec![0; 9]),
+			Error::<Test>::UriTooLong
+		);
+		assert_ok!(TemplateModule::create_anchored(Origin::signed(1), content_hash, vec![0; 8]));
+	});
+}
+
+#[test]
+fn transfer_requires_min_claim_age() {
+	new_test_ext().execute_with(|| {
+		set_min_claim_age(5);
+		run_to_block(1);
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+
+		// Verify that the claim cannot move one block before it is old enough.
+		run_to_block(5);
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), 2, claim.clone()),
+			Error::<Test>::ClaimTooYoung
+		);
+		assert_ok!(TemplateModule::propose_transfer(Origin::signed(1), claim.clone(), 2));
+		assert_noop!(
+			TemplateModule::accept_transfer(Origin::signed(2), claim.clone()),
+			Error::<Test>::ClaimTooYoung
+		);
+
+		// Verify that it can move once it is exactly old enough.
+		run_to_block(6);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_eq!(TemplateModule::proofs(key(&claim)).owner, 2);
+	});
+}
+
+#[test]
+fn successful_revoke_claim_pays_no_fee() {
+	use frame_support::weights::Pays;
+
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+
+		// Verify that failed attempts still pay.
+		let err = TemplateModule::revoke_claim(Origin::signed(2), claim.clone()).unwrap_err();
+		assert_eq!(err.post_info.pays_fee, Pays::Yes);
+		let err = TemplateModule::revoke_claim(Origin::signed(1), vec![9]).unwrap_err();
+		assert_eq!(err.post_info.pays_fee, Pays::Yes);
+
+		let post_info = TemplateModule::revoke_claim(Origin::signed(1), claim).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+	});
+}
+
+#[test]
+fn force_create_batch_skips_existing_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_noop!(
+			TemplateModule::force_create_batch(Origin::signed(1), vec![(vec![0], 2, 7)]),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::force_create_batch(
+			Origin::root(),
+			vec![(vec![0], 2, 7), (vec![1], 2, 7), (vec![2], 3, 8)],
+		));
+		assert_eq!(last_event(), RawEvent::ClaimsForceImported(2).into());
+
+		// Verify that only the new claims were written, and indexed under their owners.
+		assert_eq!(TemplateModule::proofs(key(&[0])).owner, 2);
+		assert_eq!(TemplateModule::proofs(key(&[0])).created_at, 7);
+		assert_eq!(TemplateModule::proofs(key(&[1])).owner, 1);
+		assert_eq!(TemplateModule::proofs(key(&[2])).owner, 3);
+		assert_eq!(TemplateModule::total_proofs(), 3);
+		assert_eq!(TemplateModule::claim_count_of(2), 1);
+		assert_eq!(TemplateModule::claims_of(&2), vec![key(&[0])]);
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+		assert_eq!(TemplateModule::do_try_state(), Ok(()));
+	});
+}
+
+#[test]
+fn force_create_batch_is_all_or_nothing_on_other_errors() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert!(TemplateModule::force_create_batch(Origin::root(), vec![(vec![0], 2, 7), (vec![], 2, 7)]).is_err());
+		assert_eq!(last_event(), RawEvent::BatchRejected(1).into());
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0])));
+		assert_eq!(TemplateModule::total_proofs(), 0);
+	});
+}
+
+#[test]
+fn transfer_records_block_separately_from_creation() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::last_transfer(key(&[0, 1])), None);
+
+		run_to_block(5);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimTransfered(1, 2, key(&[0, 1]), 1, 1, 5, last_seq()).into());
+
+		// Verify that the creation block is kept and the transfer block is stored alongside it.
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).created_at, 1);
+		assert_eq!(TemplateModule::last_transfer(key(&[0, 1])), Some(5));
+
+		run_to_block(8);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimTransfered(2, 3, key(&[0, 1]), 1, 5, 8, last_seq()).into());
+		assert_eq!(TemplateModule::last_transfer(key(&[0, 1])), Some(8));
+	});
+}
+
+#[test]
+fn claim_creation_is_rate_limited_per_block() {
+	new_test_ext().execute_with(|| {
+		set_max_claims_per_block(2);
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim_for(Origin::signed(1), vec![1], 2));
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), vec![2]),
+			Error::<Test>::RateLimited
+		);
+
+		// Verify that the limit is per account.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2]));
+
+		// Verify that the limit resets on the next block.
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![3]));
+		assert_ok!(TemplateModule::create_claim_for(Origin::signed(1), vec![4], 3));
+		assert_noop!(
+			TemplateModule::create_claim_for(Origin::signed(1), vec![5], 3),
+			Error::<Test>::RateLimited
+		);
+	});
+}
+
+#[test]
+fn claim_counters_track_creation_and_revocation() {
+	new_test_ext().execute_with(|| {
+		let counts = || (TemplateModule::total_proofs(), TemplateModule::claim_count_of(1), TemplateModule::claim_count_of(2));
+		assert_eq!(counts(), (0, 0, 0));
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2]));
+		assert_eq!(counts(), (3, 2, 1));
+
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![1]));
+		assert_eq!(counts(), (3, 1, 2));
+
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_eq!(counts(), (1, 0, 1));
+
+		// Verify that the counters agree with the claims actually stored.
+		assert_eq!(TemplateModule::claims_of(&2).len() as u32, TemplateModule::claim_count_of(2));
+		assert_eq!(TemplateModule::do_try_state(), Ok(()));
+	});
+}
+
+#[test]
+fn revoke_claim_with_reason_logs_reason_after_removal() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim_with_reason(Origin::signed(1), vec![0, 1], b"superseded".to_vec()));
+		assert_eq!(
+			last_event(),
+			RawEvent::ClaimRevokedWithReason(1, key(&[0, 1]), b"superseded".to_vec(), last_seq()).into()
+		);
+
+		// Verify that the claim is gone but the log entry survives it.
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[0, 1])));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(TemplateModule::revocation_log(key(&[0, 1])), Some((1, 2, b"superseded".to_vec())));
+
+		// Verify that recreating the claim keeps the log.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![0, 1]));
+		assert_eq!(TemplateModule::revocation_log(key(&[0, 1])), Some((1, 2, b"superseded".to_vec())));
+	});
+}
+
+#[test]
+fn revoke_claim_with_reason_enforces_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::revoke_claim_with_reason(Origin::signed(1), vec![0, 1], vec![0; 11]),
+			Error::<Test>::ReasonTooLong
+		);
+		assert_noop!(
+			TemplateModule::revoke_claim_with_reason(Origin::signed(2), vec![0, 1], vec![]),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(TemplateModule::revoke_claim_with_reason(Origin::signed(1), vec![0, 1], vec![0; 10]));
+	});
+}
+
+#[test]
+fn claim_ids_increment_and_resolve_both_ways() {
+	new_test_ext_with_claims(vec![(vec![9], 3)]).execute_with(|| {
+		run_to_block(1);
+		// The genesis claim takes the first id.
+		assert_eq!(TemplateModule::id_of(key(&[9])), Some(0));
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_eq!(last_event(), RawEvent::ClaimCreated(1, key(&[0]), 1, 1, 0, 1, last_seq()).into());
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_eq!(TemplateModule::id_of(key(&[1])), Some(2));
+		assert_eq!(TemplateModule::claim_by_id(2), Some(key(&[1])));
+		assert_eq!(TemplateModule::next_claim_id(), 3);
+
+		// Verify that a revoked claim's id is freed but never handed out again.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_eq!(TemplateModule::claim_by_id(1), None);
+		assert_eq!(TemplateModule::id_of(key(&[0])), None);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_eq!(TemplateModule::id_of(key(&[0])), Some(3));
+		assert_eq!(TemplateModule::claim_by_id(1), None);
+
+		// Verify that renaming keeps the id and updates the reverse lookup.
+		assert_ok!(TemplateModule::rename_claim(Origin::signed(1), vec![1], vec![2]));
+		assert_eq!(TemplateModule::id_of(key(&[2])), Some(2));
+		assert_eq!(TemplateModule::claim_by_id(2), Some(key(&[2])));
+		assert_eq!(TemplateModule::id_of(key(&[1])), None);
+	});
+}
+
+#[test]
+fn transfer_claims_moves_owned_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+
+		assert_ok!(TemplateModule::transfer_claims(Origin::signed(1), vec![vec![0], vec![1]], 2, false));
+		assert_eq!(last_event(), RawEvent::BatchClaimsTransfered(2, 2).into());
+		assert_eq!(TemplateModule::proofs(key(&[0])).owner, 2);
+		assert_eq!(TemplateModule::proofs(key(&[1])).owner, 2);
+		assert_eq!(TemplateModule::claim_count_of(1), 0);
+		assert_eq!(TemplateModule::claim_count_of(2), 2);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 20);
+		assert_eq!(TemplateModule::do_try_state(), Ok(()));
+	});
+}
+
+#[test]
+fn transfer_claims_fails_fast_on_non_owned_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+
+		assert_eq!(
+			TemplateModule::transfer_claims(Origin::signed(1), vec![vec![0], vec![1]], 3, false),
+			Err(Error::<Test>::NotAuthorized.into())
+		);
+		assert_eq!(last_event(), RawEvent::BatchRejected(1).into());
+
+		// Verify that nothing moved.
+		assert_eq!(TemplateModule::proofs(key(&[0])).owner, 1);
+		assert_eq!(TemplateModule::proofs(key(&[1])).owner, 2);
+		assert_eq!(TemplateModule::claim_count_of(3), 0);
+	});
+}
+
+#[test]
+fn transfer_claims_best_effort_skips_non_owned_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+
+		assert_ok!(TemplateModule::transfer_claims(Origin::signed(1), vec![vec![1], vec![0], vec![7]], 3, true));
+		assert_eq!(last_event(), RawEvent::BatchClaimsTransfered(3, 1).into());
+		assert_eq!(TemplateModule::proofs(key(&[0])).owner, 3);
+		assert_eq!(TemplateModule::proofs(key(&[1])).owner, 2);
+	});
+}
+
+#[test]
+fn claim_age_counts_blocks_since_creation() {
+	new_test_ext().execute_with(|| {
+		run_to_block(3);
+		assert_eq!(TemplateModule::claim_age(&[0, 1], 3), None);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::claim_age(&[0, 1], 3), Some(0));
+
+		run_to_block(10);
+		assert_eq!(TemplateModule::claim_age(&[0, 1], System::block_number()), Some(7));
+
+		// Verify that transferring the claim does not reset its age.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_eq!(TemplateModule::claim_age(&[0, 1], 10), Some(7));
+	});
+}
+
+#[test]
+fn allowlist_gates_claim_creation_when_required() {
+	new_test_ext().execute_with(|| {
+		set_require_allowlist(true);
+		run_to_block(1);
+		assert_noop!(
+			TemplateModule::add_to_allowlist(Origin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::NotAllowed
+		);
+
+		assert_ok!(TemplateModule::add_to_allowlist(Origin::root(), 1));
+		assert_eq!(last_event(), RawEvent::AddedToAllowlist(1).into());
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+
+		// Verify that owners may still transfer and revoke without being allowlisted.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+
+		assert_ok!(TemplateModule::remove_from_allowlist(Origin::root(), 1));
+		assert_eq!(last_event(), RawEvent::RemovedFromAllowlist(1).into());
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn allowlist_is_ignored_when_not_required() {
+	new_test_ext().execute_with(|| {
+		assert!(!TemplateModule::is_allowlisted(1));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+	});
+}
+
+#[test]
+fn block_activity_is_summarised_at_the_end_of_the_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2]));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 3, vec![0]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![2]));
+		// Failed operations are not counted.
+		assert!(TemplateModule::revoke_claim(Origin::signed(2), vec![1]).is_err());
+
+		TemplateModule::on_finalize(1);
+		assert_eq!(last_event(), RawEvent::BlockClaimActivity(3, 1, 1).into());
+
+		// Verify that the counters start over and a quiet block reports nothing.
+		run_to_block(2);
+		assert_eq!(TemplateModule::block_activity(), (0, 0, 0));
+		let events = System::events().len();
+		TemplateModule::on_finalize(2);
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn is_owner_matches_owners_only() {
+	new_test_ext().execute_with(|| {
+		assert!(!TemplateModule::is_owner(&[0, 1], &1));
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert!(TemplateModule::is_owner(&[0, 1], &1));
+		assert!(!TemplateModule::is_owner(&[0, 1], &2));
+
+		// Verify that co-owners count as owners.
+		assert_ok!(TemplateModule::add_owner(Origin::signed(1), vec![0, 1], 2));
+		assert!(TemplateModule::is_owner(&[0, 1], &2));
+	});
+}
+
+#[test]
+fn inputs_at_their_length_limits_round_trip_through_storage() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![7; 16];
+		assert_noop!(
+			TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![7; 17], vec![1; 8]),
+			Error::<Test>::ClaimTooLong
+		);
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), claim.clone(), vec![1; 8]));
+		assert_eq!(TemplateModule::metadata_of(key(&claim)), vec![1; 8]);
+
+		assert_eq!(
+			TemplateModule::revoke_claim_with_reason(Origin::signed(1), claim.clone(), vec![2; 11]),
+			Err(Error::<Test>::ReasonTooLong.into())
+		);
+		assert_ok!(TemplateModule::revoke_claim_with_reason(Origin::signed(1), claim.clone(), vec![2; 10]));
+		assert_eq!(TemplateModule::revocation_log(key(&claim)), Some((1, 0, vec![2; 10])));
+	});
+}
+
+#[test]
+fn escrow_claim_moves_claim_to_escrow_account() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let claim = vec![0, 1];
+		let escrow = TemplateModule::escrow_account();
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(TemplateModule::escrow_claim(Origin::signed(2), claim.clone()), Error::<Test>::NotClaimOwner);
+
+		assert_ok!(TemplateModule::escrow_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(last_event(), RawEvent::ClaimEscrowed(1, key(&claim), last_seq()).into());
+		assert_eq!(TemplateModule::proofs(key(&claim)).owner, escrow);
+		assert_eq!(TemplateModule::escrowed_by(key(&claim)), Some(1));
+
+		// Verify that the deposit stays reserved from the original owner.
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(escrow), 0);
+
+		// Claims can only enter escrow through `escrow_claim`.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2, 3]));
+		assert_noop!(
+			TemplateModule::transfer_claim(Origin::signed(1), escrow, vec![2, 3]),
+			Error::<Test>::CannotTransferToEscrow
+		);
+	});
+}
+
+#[test]
+fn release_claim_requires_escrow_origin() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(TemplateModule::release_claim(Origin::root(), claim.clone(), 1), Error::<Test>::NotEscrowed);
+
+		assert_ok!(TemplateModule::escrow_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(TemplateModule::release_claim(Origin::signed(1), claim.clone(), 1), DispatchError::BadOrigin);
+		assert_eq!(TemplateModule::escrowed_by(key(&claim)), Some(1));
+	});
+}
+
+#[test]
+fn release_claim_returns_claim_to_original_owner() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(TemplateModule::escrow_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(TemplateModule::release_claim(Origin::root(), claim.clone(), 1));
+		assert_eq!(last_event(), RawEvent::ClaimReleased(key(&claim), 1, last_seq()).into());
+		assert_eq!(TemplateModule::proofs(key(&claim)).owner, 1);
+		assert_eq!(TemplateModule::escrowed_by(key(&claim)), None);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(TemplateModule::claim_count_of(TemplateModule::escrow_account()), 0);
+	});
+}
+
+#[test]
+fn claims_created_in_block_lists_each_blocks_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2, 3]));
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![4, 5]));
+
+		assert_eq!(TemplateModule::claims_created_in_block(1), vec![key(&[0, 1]), key(&[2, 3])]);
+		assert_eq!(TemplateModule::claims_created_in_block(2), vec![key(&[4, 5])]);
+
+		// Verify that revoking a claim leaves its creation record intact.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::claims_created_in_block(1), vec![key(&[0, 1]), key(&[2, 3])]);
+	});
+}
+
+#[test]
+fn create_claim_fails_once_the_block_is_full() {
+	new_test_ext().execute_with(|| {
+		set_max_created_per_block(2);
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2, 3]));
+
+		// Verify that a third claim in the same block is rejected rather than left unrecorded, even
+		// after a claim recorded in this block is revoked.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(3), vec![4, 5]),
+			Error::<Test>::BlockClaimsFull
+		);
+		assert_noop!(
+			TemplateModule::force_create(Origin::root(), vec![4, 5], 3, 1),
+			Error::<Test>::BlockClaimsFull
+		);
+
+		// Verify that the claim can be created in the next block.
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(3), vec![4, 5]));
+		assert_eq!(TemplateModule::claims_created_in_block(1), vec![key(&[0, 1]), key(&[2, 3])]);
+		assert_eq!(TemplateModule::claims_created_in_block(2), vec![key(&[4, 5])]);
+	});
+}
+
+#[test]
+fn set_alias_resolves_to_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_noop!(
+			TemplateModule::set_alias(Origin::signed(2), vec![0, 1], b"deed".to_vec()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			TemplateModule::set_alias(Origin::signed(1), vec![0, 1], vec![0; 9]),
+			Error::<Test>::AliasTooLong
+		);
+		assert_noop!(
+			TemplateModule::set_alias(Origin::signed(1), vec![0, 1], vec![]),
+			Error::<Test>::AliasEmpty
+		);
+
+		assert_ok!(TemplateModule::set_alias(Origin::signed(1), vec![0, 1], b"deed".to_vec()));
+		assert_eq!(last_event(), RawEvent::AliasSet(key(&[0, 1]), b"deed".to_vec(), last_seq()).into());
+		assert_eq!(TemplateModule::resolve_alias(b"deed"), Some(key(&[0, 1])));
+		assert_eq!(TemplateModule::resolve_alias(b"will"), None);
+
+		// Verify that a new alias replaces the old one.
+		assert_ok!(TemplateModule::set_alias(Origin::signed(1), vec![0, 1], b"will".to_vec()));
+		assert_eq!(TemplateModule::resolve_alias(b"deed"), None);
+		assert_eq!(TemplateModule::resolve_alias(b"will"), Some(key(&[0, 1])));
+	});
+}
+
+#[test]
+fn set_alias_enforces_uniqueness() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2, 3]));
+		assert_ok!(TemplateModule::set_alias(Origin::signed(1), vec![0, 1], b"deed".to_vec()));
+		assert_noop!(
+			TemplateModule::set_alias(Origin::signed(2), vec![2, 3], b"deed".to_vec()),
+			Error::<Test>::AliasTaken
+		);
+
+		// Setting the same alias again on its own claim is allowed.
+		assert_ok!(TemplateModule::set_alias(Origin::signed(1), vec![0, 1], b"deed".to_vec()));
+		assert_eq!(TemplateModule::resolve_alias(b"deed"), Some(key(&[0, 1])));
+	});
+}
+
+#[test]
+fn revoke_claim_clears_alias() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_alias(Origin::signed(1), vec![0, 1], b"deed".to_vec()));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::resolve_alias(b"deed"), None);
+		assert_eq!(TemplateModule::alias_of(key(&[0, 1])), None);
+
+		// Verify that the freed alias can be taken by another claim.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2, 3]));
+		assert_ok!(TemplateModule::set_alias(Origin::signed(2), vec![2, 3], b"deed".to_vec()));
+	});
+}
+
+#[test]
+fn revoke_within_grace_period_refunds_whole_deposit() {
+	new_test_ext().execute_with(|| {
+		set_late_revoke_penalty(Perbill::from_percent(50));
+		run_to_block(1);
+		let treasury = TemplateModule::treasury_account();
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		let treasury_balance = Balances::free_balance(treasury);
+
+		run_to_block(6);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimRevoked(1, key(&[0, 1]), 6, 10, 0, last_seq()).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 95);
+		assert_eq!(Balances::free_balance(treasury), treasury_balance);
+	});
+}
+
+#[test]
+fn revoke_after_grace_period_pays_penalty_to_treasury() {
+	new_test_ext().execute_with(|| {
+		set_late_revoke_penalty(Perbill::from_percent(50));
+		run_to_block(1);
+		let treasury = TemplateModule::treasury_account();
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2, 3]));
+		let treasury_balance = Balances::free_balance(treasury);
+
+		run_to_block(7);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimRevoked(1, key(&[0, 1]), 7, 5, 5, last_seq()).into());
+		assert_eq!(Balances::free_balance(treasury), treasury_balance + 5);
+
+		// Verify that the deposit of the other claim is still reserved.
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::free_balance(1), 100 - 2 * 5 - 10 - 5);
+	});
+}
+
+#[test]
+fn derive_key_matches_storage_key() {
+	new_test_ext().execute_with(|| {
+		// Verify that the key is stable across calls and is the hash of the raw claim.
+		assert_eq!(TemplateModule::derive_key(&[0, 1]), TemplateModule::derive_key(&[0, 1]));
+		assert_eq!(TemplateModule::derive_key(&[0, 1]), BlakeTwo256::hash(&[0, 1]));
+		assert_ne!(TemplateModule::derive_key(&[0, 1]), TemplateModule::derive_key(&[1, 0]));
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::proofs(TemplateModule::derive_key(&[0, 1])).owner, 1);
+		assert_eq!(TemplateModule::claims_of_paged(&1, None, 10).0, vec![TemplateModule::derive_key(&[0, 1]).encode()]);
+	});
+}
+
+#[test]
+fn force_create_batch_requires_force_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::force_create_batch(Origin::signed(1), vec![(vec![0], 2, 7)]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::force_create_batch(Origin::root(), vec![(vec![0], 2, 7)]));
+		assert_eq!(TemplateModule::proofs(key(&[0])).owner, 2);
+	});
+}
+
+#[test]
+fn transfer_claim_if_unchanged_works_when_creation_block_matches() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::transfer_claim_if_unchanged(Origin::signed(1), vec![0, 1], 2, 1));
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).owner, 2);
+	});
+}
+
+#[test]
+fn transfer_claim_if_unchanged_rejects_recreated_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+
+		// The claim is revoked and created again after the caller read it.
+		run_to_block(2);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+
+		assert_noop!(
+			TemplateModule::transfer_claim_if_unchanged(Origin::signed(1), vec![0, 1], 2, 1),
+			Error::<Test>::ClaimChanged
+		);
+		assert_noop!(
+			TemplateModule::transfer_claim_if_unchanged(Origin::signed(1), vec![2, 3], 2, 1),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn claim_events_carry_only_hash_by_default() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(
+			last_event(),
+			RawEvent::ClaimCreated(1, TemplateModule::derive_key(&[0, 1]), 2, 1, 0, 0, last_seq()).into()
+		);
+	});
+}
+
+#[test]
+fn claim_events_carry_bytes_unless_hashed_only() {
+	new_test_ext().execute_with(|| {
+		set_emit_hashed_events_only(false);
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::ClaimBytes(TemplateModule::derive_key(&[0, 1]), vec![0, 1], last_seq()).into());
+
+		// Claims created by hash have no bytes to publish.
+		assert_ok!(TemplateModule::create_claim_hash(Origin::signed(1), key(&[2, 3])));
+		assert_eq!(
+			last_event(),
+			RawEvent::ClaimCreated(1, key(&[2, 3]), 32, 1, 0, 1, last_seq()).into()
+		);
+	});
+}
+
+#[test]
+fn create_claim_idempotent_ignores_resubmitted_nonce() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_idempotent(Origin::signed(1), vec![0, 1], 0));
+		assert_eq!(TemplateModule::last_nonce_of(1), Some((0, key(&[0, 1]))));
+
+		// Verify that the retry succeeds without creating or charging anything.
+		let free = Balances::free_balance(1);
+		assert_ok!(TemplateModule::create_claim_idempotent(Origin::signed(1), vec![0, 1], 0));
+		assert_eq!(Balances::free_balance(1), free);
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+
+		// The nonce is tracked per sender.
+		assert_noop!(
+			TemplateModule::create_claim_idempotent(Origin::signed(2), vec![0, 1], 0),
+			Error::<Test>::ProofAlreadyExist
+		);
+	});
+}
+
+#[test]
+fn create_claim_idempotent_with_new_nonce_still_rejects_existing_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_idempotent(Origin::signed(1), vec![0, 1], 0));
+		assert_noop!(
+			TemplateModule::create_claim_idempotent(Origin::signed(1), vec![0, 1], 1),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(TemplateModule::last_nonce_of(1), Some((0, key(&[0, 1]))));
+
+		assert_ok!(TemplateModule::create_claim_idempotent(Origin::signed(1), vec![2, 3], 1));
+		assert_eq!(TemplateModule::last_nonce_of(1), Some((1, key(&[2, 3]))));
+	});
+}
+
+#[test]
+fn create_claim_idempotent_rejects_reused_nonce_for_another_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim_idempotent(Origin::signed(1), vec![0, 1], 0));
+		assert_noop!(
+			TemplateModule::create_claim_idempotent(Origin::signed(1), vec![2, 3], 0),
+			Error::<Test>::NonceReused
+		);
+		assert!(TemplateModule::get_claim(&[2, 3]).is_none());
+	});
+}
+
+// Resolve an owner the way a pallet depending only on `T: Trait` would.
+fn owner_through_trait<T: crate::Trait>(claim: &[u8]) -> Option<T::AccountId> {
+	crate::Module::<T>::owner_of(claim)
+}
+
+#[test]
+fn owner_of_resolves_owner_for_other_pallets() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(owner_through_trait::<Test>(&[0, 1]), None);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(owner_through_trait::<Test>(&[0, 1]), Some(1));
+
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_eq!(TemplateModule::owner_of(&[0, 1]), Some(2));
+	});
+}
+
+#[test]
+fn upheld_challenge_slashes_deposit_to_treasury() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let treasury = TemplateModule::treasury_account();
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2, 3]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+		let treasury_balance = Balances::free_balance(treasury);
+		let free = Balances::free_balance(1);
+
+		assert_ok!(TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], true));
+		assert!(System::events().iter().any(|record| matches!(record.event, TestEvent::poe(RawEvent::ClaimSlashed(1, 10, _)))));
+
+		// Verify that exactly the deposit of the fraudulent claim was slashed.
+		assert_eq!(Balances::free_balance(treasury), treasury_balance + 10);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::free_balance(1), free);
+	});
+}
+
+#[test]
+fn rejected_challenge_leaves_deposit_reserved() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::challenge_claim(Origin::signed(2), vec![0, 1]));
+
+		assert_ok!(TemplateModule::resolve_challenge(Origin::root(), vec![0, 1], false));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])).deposit, 10);
+	});
+}
+
+#[test]
+fn normalized_keys_collide_across_case_and_trailing_zeros() {
+	new_test_ext().execute_with(|| {
+		set_normalize_keys(true);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), b"Foo".to_vec()));
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(2), b"foo".to_vec()),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(TemplateModule::derive_key(b"FOO\0\0"), key(b"foo"));
+
+		// Verify that every call resolves the same logical claim.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, b"fOO\0".to_vec()));
+		assert_eq!(TemplateModule::owner_of(b"foo"), Some(2));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), b"FOO".to_vec()));
+		assert_eq!(TemplateModule::owner_of(b"Foo"), None);
+	});
+}
+
+#[test]
+fn keys_stay_distinct_without_normalization() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), b"Foo".to_vec()));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), b"foo".to_vec()));
+		assert_eq!(TemplateModule::owner_of(b"Foo"), Some(1));
+		assert_eq!(TemplateModule::owner_of(b"foo"), Some(2));
+		assert_eq!(TemplateModule::owner_of(b"foo\0"), None);
+	});
+}
+
+#[test]
+fn config_values_are_exposed_as_constants() {
+	use frame_support::metadata::DecodeDifferent;
+
+	let constant = |name: &'static str| {
+		let metadata = TemplateModule::module_constants_metadata()
+			.iter()
+			.find(|constant| constant.name == DecodeDifferent::Encode(name))
+			.expect("the constant is exposed");
+		match &metadata.value {
+			DecodeDifferent::Encode(getter) => getter.0.default_byte(),
+			DecodeDifferent::Decoded(value) => value.clone(),
+		}
+	};
+	assert_eq!(constant("MaxClaimLength"), 16u32.encode());
+	assert_eq!(constant("MaxClaimsPerAccount"), 3u32.encode());
+	assert_eq!(constant("ClaimDeposit"), 10u64.encode());
+}
+
+#[test]
+fn revoke_if_expired_rewards_caller() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 2));
+		// Skip ahead without running `on_idle`, which would prune the claim.
+		System::set_block_number(3);
+
+		assert_ok!(TemplateModule::revoke_if_expired(Origin::signed(2), vec![0, 1]));
+		assert_eq!(last_event(), RawEvent::CleanupRewarded(2, key(&[0, 1]), 3, last_seq()).into());
+		assert_eq!(TemplateModule::owner_of(&[0, 1]), None);
+
+		// Verify that the caller got the reward and the owner the rest of the deposit.
+		assert_eq!(Balances::free_balance(2), 103);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100 - 5 - 3);
+	});
+}
+
+#[test]
+fn revoke_if_expired_rejects_live_claims() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![2, 3], 2));
+		assert_noop!(
+			TemplateModule::revoke_if_expired(Origin::signed(2), vec![0, 1]),
+			Error::<Test>::ClaimNotExpired
+		);
+		assert_noop!(
+			TemplateModule::revoke_if_expired(Origin::signed(2), vec![2, 3]),
+			Error::<Test>::ClaimNotExpired
+		);
+		assert_noop!(
+			TemplateModule::revoke_if_expired(Origin::signed(2), vec![4, 5]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn failed_transfer_leaves_claim_and_emits_failure() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		let before = TemplateModule::proofs(key(&[0, 1]));
+
+		let error: DispatchError = Error::<Test>::InsufficientBalanceForDeposit.into();
+		assert_eq!(TemplateModule::transfer_claim(Origin::signed(1), 4, vec![0, 1]), Err(error));
+		let code = match error {
+			DispatchError::Module { error, .. } => error,
+			_ => unreachable!(),
+		};
+		assert_eq!(last_event(), RawEvent::ClaimTransferFailed(1, 4, key(&[0, 1]), code, last_seq()).into());
+
+		// Verify that the claim and its deposit stay with the original owner.
+		assert_eq!(TemplateModule::proofs(key(&[0, 1])), before);
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+		assert_eq!(TemplateModule::claim_count_of(4), 0);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(TemplateModule::last_transfer(key(&[0, 1])), None);
+	});
+}
+
+#[test]
+fn can_create_reports_why_a_create_would_fail() {
+	new_test_ext().execute_with(|| {
+		let code = |error: Error<Test>| match DispatchError::from(error) {
+			DispatchError::Module { error, .. } => error,
+			_ => unreachable!(),
+		};
+
+		assert_eq!(TemplateModule::can_create(&[0, 1], &1), Ok(()));
+		assert_eq!(TemplateModule::can_create(&[], &1), Err(code(Error::<Test>::ClaimTooShort)));
+		assert_eq!(TemplateModule::can_create(&[0; 17], &1), Err(code(Error::<Test>::ClaimTooLong)));
+		assert_eq!(
+			TemplateModule::can_create(&[0, 1], &4),
+			Err(code(Error::<Test>::InsufficientBalanceForDeposit))
+		);
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::can_create(&[0, 1], &2), Err(code(Error::<Test>::ProofAlreadyExist)));
+
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 2]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 3]));
+		assert_eq!(TemplateModule::can_create(&[0, 4], &1), Err(code(Error::<Test>::TooManyClaims)));
+
+		set_require_allowlist(true);
+		assert_eq!(TemplateModule::can_create(&[0, 4], &2), Err(code(Error::<Test>::NotAllowed)));
+		assert_ok!(TemplateModule::add_to_allowlist(Origin::root(), 2));
+		assert_eq!(TemplateModule::can_create(&[0, 4], &2), Ok(()));
+
+		// Verify that the checks left no trace behind.
+		assert_eq!(TemplateModule::claim_count_of(2), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn peak_proofs_tracks_the_highest_total_reached() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::peak_proofs(), 0);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2]));
+		assert_eq!(TemplateModule::peak_proofs(), 3);
+
+		// Verify that revoking claims leaves the peak alone.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![1]));
+		assert_eq!(TemplateModule::total_proofs(), 1);
+		assert_eq!(TemplateModule::peak_proofs(), 3);
+
+		// Creating claims up to the old peak does not raise it, but going past it does.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![3]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![4]));
+		assert_eq!(TemplateModule::peak_proofs(), 3);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(3), vec![5]));
+		assert_eq!(TemplateModule::total_proofs(), 4);
+		assert_eq!(TemplateModule::peak_proofs(), 4);
+	});
+}
+
+#[test]
+fn create_claim_with_header_enforces_max_version() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_claim_with_header(Origin::signed(1), vec![0, 1], ClaimHeader { version: 3, kind: 1 }),
+			Error::<Test>::UnsupportedClaimVersion
+		);
+		assert_ok!(TemplateModule::create_claim_with_header(
+			Origin::signed(1),
+			vec![0, 1],
+			ClaimHeader { version: 2, kind: 1 }
+		));
+	});
+}
+
+#[test]
+fn claim_header_round_trips_through_storage_and_events() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let header = ClaimHeader { version: 1, kind: 7 };
+		assert_ok!(TemplateModule::create_claim_with_header(Origin::signed(1), vec![0, 1], header));
+		assert_eq!(last_event(), RawEvent::ClaimHeaderSet(key(&[0, 1]), header, last_seq()).into());
+		assert_eq!(TemplateModule::claim_header(&[0, 1]), Some(header));
+
+		// Claims created without a header read as having the default one.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 2]));
+		assert_eq!(TemplateModule::claim_header(&[0, 2]), Some(ClaimHeader::default()));
+		assert_eq!(TemplateModule::claim_header(&[0, 3]), None);
+
+		// Verify that the header is removed with the claim.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(TemplateModule::claim_header(&[0, 1]), None);
+		assert_eq!(TemplateModule::header_of(key(&[0, 1])), ClaimHeader::default());
+	});
+}
+
+#[test]
+fn on_idle_prunes_expired_claims_with_ample_weight() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(2), vec![1], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(2), vec![2], 5));
+
+		// Verify that `on_initialize` no longer prunes anything.
+		System::set_block_number(2);
+		TemplateModule::on_initialize(2);
+		assert_eq!(TemplateModule::total_proofs(), 3);
+
+		let consumed = TemplateModule::on_idle(2, Weight::max_value());
+		assert!(consumed > 0);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+		assert_eq!(TemplateModule::get_claim(&[0]), None);
+		assert_eq!(TemplateModule::get_claim(&[1]), None);
+		assert!(TemplateModule::get_claim(&[2]).is_some());
+	});
+}
+
+#[test]
+fn on_idle_prunes_nothing_with_tiny_weight() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		System::set_block_number(2);
+
+		assert_eq!(TemplateModule::on_idle(2, 1_000), 0);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+
+		// The mock block has no room for pruning either, so the claim waits for an idle block.
+		TemplateModule::on_finalize(2);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+	});
+}
+
+#[test]
+fn on_idle_walks_a_bounded_number_of_blocks() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0], 1));
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![1], 9));
+		assert_eq!(TemplateModule::expiring_at(2), vec![key(&[0])]);
+
+		// After a long gap the pruning catches up a bounded number of blocks at a time.
+		System::set_block_number(500);
+		TemplateModule::on_idle(500, Weight::max_value());
+		assert_eq!(TemplateModule::get_claim(&[0]), None);
+		assert_eq!(TemplateModule::get_claim(&[1]), None);
+		let cursor = TemplateModule::prune_cursor();
+		assert!(cursor > 10 && cursor < 2 + MAX_EXPIRY_SCAN as u64);
+
+		for _ in 0..10 {
+			TemplateModule::on_idle(500, Weight::max_value());
+		}
+		assert_eq!(TemplateModule::prune_cursor(), 501);
+	});
+}
+
+#[test]
+fn replace_claim_corrects_a_mistyped_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		Timestamp::set_timestamp(100);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+
+		run_to_block(3);
+		assert_ok!(TemplateModule::replace_claim(Origin::signed(1), vec![0, 1], vec![1, 0]));
+		assert_eq!(last_event(), RawEvent::ClaimReplaced(1, key(&[0, 1]), key(&[1, 0]), last_seq()).into());
+
+		// Verify that the old claim is gone and the new one keeps its owner and creation time.
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), None);
+		assert_eq!(
+			TemplateModule::proofs(key(&[1, 0])),
+			ClaimInfo { owner: 1, creator: 1, created_at: 1, timestamp: 100, deposit: 10 }
+		);
+		assert_eq!(TemplateModule::claims_of(&1), vec![key(&[1, 0])]);
+		assert_eq!(TemplateModule::claim_count_of(1), 1);
+		assert_eq!(TemplateModule::total_proofs(), 1);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn replace_claim_failed_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::replace_claim(Origin::signed(1), vec![0, 1], vec![0, 2]),
+			Error::<Test>::ClaimNotExist
+		);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![0, 2]));
+		assert_noop!(
+			TemplateModule::replace_claim(Origin::signed(2), vec![0, 1], vec![0, 3]),
+			Error::<Test>::NotClaimOwner
+		);
+
+		// A failed replace leaves both claims exactly as they were.
+		let before = (TemplateModule::proofs(key(&[0, 1])), TemplateModule::proofs(key(&[0, 2])));
+		assert_noop!(
+			TemplateModule::replace_claim(Origin::signed(1), vec![0, 1], vec![0, 2]),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!((TemplateModule::proofs(key(&[0, 1])), TemplateModule::proofs(key(&[0, 2]))), before);
+		assert_noop!(
+			TemplateModule::replace_claim(Origin::signed(1), vec![0, 1], vec![]),
+			Error::<Test>::ClaimTooShort
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v4_carries_on_across_blocks() {
+	new_test_ext().execute_with(|| {
+		// Synthesize more entries in the old tuple layout than are migrated in one block.
+		crate::StorageVersion::put(crate::Releases::V1);
+		for i in 0..5u8 {
+			frame_support::storage::unhashed::put(
+				&crate::Proofs::<Test>::hashed_key_for(key(&[i])),
+				&(1u64 + i as u64, 5u64, 10u64),
+			);
+		}
+
+		TemplateModule::on_runtime_upgrade();
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V1);
+		assert!(TemplateModule::migration_cursor().is_some());
+
+		run_to_block(1);
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V1);
+		assert!(TemplateModule::migration_cursor().is_some());
+
+		// Verify that the last chunk finishes the migration and the claim count follows.
+		run_to_block(2);
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert_eq!(TemplateModule::migration_cursor(), None);
+		assert_eq!(TemplateModule::total_proofs(), 5);
+		for i in 0..5u8 {
+			assert_eq!(
+				TemplateModule::proofs(key(&[i])),
+				ClaimInfo { owner: 1 + i as u64, creator: 1 + i as u64, created_at: 5, timestamp: 0, deposit: 10 }
+			);
+		}
+
+		// Later blocks leave the migrated claims alone.
+		run_to_block(3);
+		assert_eq!(TemplateModule::proofs(key(&[0])).owner, 1);
+	});
+}
+
+#[test]
+fn claims_created_during_a_migration_survive_it() {
+	new_test_ext().execute_with(|| {
+		crate::StorageVersion::put(crate::Releases::V1);
+		for i in 0..4u8 {
+			frame_support::storage::unhashed::put(
+				&crate::Proofs::<Test>::hashed_key_for(key(&[i])),
+				&(2u64, 5u64, 0u64),
+			);
+		}
+		TemplateModule::on_runtime_upgrade();
+		let cursor = TemplateModule::migration_cursor().expect("the migration is unfinished");
+
+		// Create a claim in the current layout that the cursor has yet to reach.
+		let claim = (0..=255u8)
+			.map(|b| vec![9, b])
+			.find(|claim| {
+				crate::Proofs::<Test>::hashed_key_for(key(claim)) > crate::Proofs::<Test>::hashed_key_for(cursor)
+			})
+			.unwrap();
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		let info = TemplateModule::proofs(key(&claim));
+		assert_eq!(info.owner, 1);
+
+		// And one the cursor has passed already, which is counted straight away.
+		let behind = (0..=255u8)
+			.map(|b| vec![8, b])
+			.find(|claim| {
+				crate::Proofs::<Test>::hashed_key_for(key(claim)) < crate::Proofs::<Test>::hashed_key_for(cursor)
+			})
+			.unwrap();
+		let counted = TemplateModule::total_proofs();
+		assert_ok!(TemplateModule::create_claim(Origin::signed(3), behind));
+		assert_eq!(TemplateModule::total_proofs(), counted + 1);
+
+		run_to_block(2);
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert_eq!(TemplateModule::proofs(key(&claim)), info);
+		assert_eq!(TemplateModule::total_proofs(), 6);
+		assert_eq!(TemplateModule::do_try_state(), Ok(()));
+
+		// The claim is still fully usable, deposit included.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), claim));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn expired_claims_are_kept_while_migrating() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![7], 1));
+
+		crate::StorageVersion::put(crate::Releases::V1);
+		for i in 0..4u8 {
+			frame_support::storage::unhashed::put(
+				&crate::Proofs::<Test>::hashed_key_for(key(&[i])),
+				&(2u64, 5u64, 0u64),
+			);
+		}
+		TemplateModule::on_runtime_upgrade();
+		assert!(TemplateModule::migration_cursor().is_some());
+
+		// Verify that neither pruning nor a signed call removes the expired claim meanwhile.
+		System::set_block_number(3);
+		TemplateModule::on_idle(3, Weight::max_value());
+		assert!(crate::Proofs::<Test>::contains_key(key(&[7])));
+		assert_noop!(
+			TemplateModule::revoke_if_expired(Origin::signed(2), vec![7]),
+			Error::<Test>::MigrationInProgress
+		);
+
+		// Once the migration is done the claim is pruned and the count stays right.
+		run_to_block(5);
+		assert_eq!(crate::StorageVersion::get(), crate::Releases::V5);
+		assert!(!crate::Proofs::<Test>::contains_key(key(&[7])));
+		assert_eq!(TemplateModule::total_proofs(), 4);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn claim_details_bundles_everything_about_a_claim() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::claim_details(&[0, 1]), None);
+
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_expiry(Origin::signed(1), vec![0, 1], 8));
+		assert_ok!(TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], b"doc".to_vec()));
+		assert_ok!(TemplateModule::lock_claim(Origin::signed(1), vec![0, 1], 6));
+		assert_ok!(TemplateModule::freeze_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(
+			TemplateModule::claim_details(&[0, 1]),
+			Some(ClaimDetails {
+				owner: 1,
+				created_at: 1,
+				expiry: Some(9),
+				metadata: Some(b"doc".to_vec()),
+				frozen: true,
+				locked_until: Some(6),
+			})
+		);
+
+		// Verify that the optional fields of a plain claim are absent.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![0, 2]));
+		assert_eq!(
+			TemplateModule::claim_details(&[0, 2]),
+			Some(ClaimDetails {
+				owner: 2,
+				created_at: 1,
+				expiry: None,
+				metadata: None,
+				frozen: false,
+				locked_until: None,
+			})
+		);
+	});
+}
+
+#[test]
+fn revoking_in_the_creation_block_emits_same_block_conflict() {
+	new_test_ext().execute_with(|| {
+		let conflicts = || System::events().into_iter()
+			.filter(|record| matches!(record.event, TestEvent::poe(RawEvent::SameBlockConflict(..))))
+			.count();
+
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(conflicts(), 1);
+		assert!(System::events().iter().any(|record| {
+			matches!(record.event, TestEvent::poe(RawEvent::SameBlockConflict(1, hash, _)) if hash == key(&[0, 1]))
+		}));
+
+		// Verify that a claim revoked in a later block is not flagged.
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 2]));
+		run_to_block(2);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), vec![0, 2]));
+		assert_eq!(conflicts(), 1);
+	});
+}
+
+#[test]
+fn claim_fee_grows_with_the_number_of_claims() {
+	new_test_ext().execute_with(|| {
+		set_fee_slope(2);
+		let treasury = TemplateModule::treasury_account();
+
+		// Verify that the fee is `FeeBase + TotalProofs * FeeSlope` at each step.
+		assert_eq!(TemplateModule::claim_fee(), 5);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0]));
+		assert_eq!(Balances::free_balance(treasury), 5);
+		assert_eq!(Balances::free_balance(1), 100 - 10 - 5);
+
+		assert_eq!(TemplateModule::claim_fee(), 7);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![1]));
+		assert_eq!(Balances::free_balance(treasury), 5 + 7);
+		assert_eq!(Balances::free_balance(2), 100 - 10 - 7);
+
+		assert_eq!(TemplateModule::claim_fee(), 9);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(3), vec![2]));
+		assert_eq!(Balances::free_balance(treasury), 5 + 7 + 9);
+
+		// The fee falls again as claims are revoked.
+		assert_eq!(TemplateModule::claim_fee(), 11);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(3), vec![2]));
+		assert_eq!(TemplateModule::claim_fee(), 9);
+	});
+}
+
+#[test]
+fn claims_in_range_returns_claims_created_in_the_blocks() {
+	new_test_ext().execute_with(|| {
+		for block in 1..=4u8 {
+			run_to_block(block as u64);
+			assert_ok!(TemplateModule::create_claim(Origin::signed(block as u64 % 3 + 1), vec![block, 0]));
+		}
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![2, 1]));
+		System::set_block_number(4);
+
+		assert_eq!(
+			TemplateModule::claims_in_range(2, 3, 10),
+			(vec![key(&[2, 0]), key(&[2, 1]), key(&[3, 0])], None)
+		);
+		assert_eq!(TemplateModule::claims_in_range(4, 4, 10), (vec![key(&[4, 0])], None));
+		assert_eq!(TemplateModule::claims_in_range(0, 100, 10).0.len(), 5);
+
+		// Verify that a page ends after the block that reaches `limit`, and carries on after it.
+		assert_eq!(TemplateModule::claims_in_range(1, 4, 1), (vec![key(&[1, 0])], Some(2)));
+		assert_eq!(TemplateModule::claims_in_range(2, 4, 1), (vec![key(&[2, 0]), key(&[2, 1])], Some(3)));
+		assert_eq!(TemplateModule::claims_in_range(1, 4, 0), (vec![], Some(1)));
+
+		// An inverted range is empty.
+		assert_eq!(TemplateModule::claims_in_range(3, 2, 10), (vec![], None));
+	});
+}
+
+#[test]
+fn claims_in_range_scans_at_most_max_range_span_blocks() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		let last = 3 * MAX_RANGE_SPAN as u64;
+		System::set_block_number(last);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 2]));
+
+		// A sparse range is walked in pages of `MAX_RANGE_SPAN` blocks.
+		let span = MAX_RANGE_SPAN as u64;
+		assert_eq!(TemplateModule::claims_in_range(0, u64::max_value(), 10), (vec![key(&[0, 1])], Some(span)));
+		assert_eq!(TemplateModule::claims_in_range(span, u64::max_value(), 10), (vec![], Some(2 * span)));
+		assert_eq!(TemplateModule::claims_in_range(2 * span, u64::max_value(), 10), (vec![], Some(last)));
+		assert_eq!(TemplateModule::claims_in_range(last, u64::max_value(), 10), (vec![key(&[0, 2])], None));
+	});
+}
+
+#[test]
+fn get_claims_returns_results_in_order() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		run_to_block(2);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![0, 3]));
+
+		assert_eq!(
+			TemplateModule::get_claims(vec![vec![0, 2], vec![0, 3], vec![0, 1], vec![0, 2]]),
+			Ok(vec![None, Some((2, 2)), Some((1, 1)), None])
+		);
+		assert_eq!(TemplateModule::get_claims(vec![]), Ok(vec![]));
+	});
+}
+
+#[test]
+fn get_claims_failed_beyond_max_query_batch() {
+	new_test_ext().execute_with(|| {
+		let code = match DispatchError::from(Error::<Test>::QueryBatchTooLarge) {
+			DispatchError::Module { error, .. } => error,
+			_ => unreachable!(),
+		};
+		assert_eq!(TemplateModule::get_claims(vec![vec![0]; 5]), Err(code));
+		assert_eq!(TemplateModule::get_claims(vec![vec![0]; 4]), Ok(vec![None; 4]));
+	});
+}
+
+#[test]
+fn ownership_distribution_buckets_accounts_by_claim_count() {
+	new_test_ext().execute_with(|| {
+		for claim in 0..3u8 {
+			assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![1, claim]));
+		}
+		assert_ok!(TemplateModule::create_claim(Origin::signed(2), vec![2, 0]));
+		assert_ok!(TemplateModule::create_claim(Origin::signed(3), vec![3, 0]));
+
+		assert_eq!(TemplateModule::ownership_distribution(3), vec![2, 0, 1]);
+		assert_eq!(TemplateModule::ownership_distribution(4), vec![2, 0, 1, 0]);
+
+		// Verify that the last bucket counts every account owning at least as many claims.
+		assert_eq!(TemplateModule::ownership_distribution(2), vec![2, 1]);
+		assert_eq!(TemplateModule::ownership_distribution(1), vec![3]);
+		assert!(TemplateModule::ownership_distribution(0).is_empty());
+
+		// An account whose claims were all revoked is not counted.
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![2, 0]));
+		assert_eq!(TemplateModule::ownership_distribution(3), vec![1, 0, 1]);
+
+		// The number of buckets is bounded.
+		assert_eq!(TemplateModule::ownership_distribution(u32::max_value()).len(), MAX_OWNERSHIP_BUCKETS as usize);
+	});
+}
+
+#[test]
+fn transfer_claim_v2_can_clear_metadata() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], b"notes".to_vec()));
+		assert_ok!(TemplateModule::transfer_claim_v2(Origin::signed(1), 2, vec![0, 1], true));
+		assert_eq!(last_event(), RawEvent::TransferMetadataHandled(key(&[0, 1]), true, last_seq()).into());
+		assert_eq!(TemplateModule::get_claim(&[0, 1]), Some((2, 1)));
+		assert!(TemplateModule::metadata_of(key(&[0, 1])).is_empty());
+	});
+}
+
+#[test]
+fn transfer_claim_v2_and_transfer_claim_can_keep_metadata() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim_with_metadata(Origin::signed(1), vec![0, 1], b"doc".to_vec()));
+		assert_ok!(TemplateModule::transfer_claim_v2(Origin::signed(1), 2, vec![0, 1], false));
+		assert_eq!(last_event(), RawEvent::TransferMetadataHandled(key(&[0, 1]), false, last_seq()).into());
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 1])), b"doc".to_vec());
+
+		// Verify that the plain transfer keeps the metadata too.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(2), 3, vec![0, 1]));
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 1])), b"doc".to_vec());
+
+		// A failed transfer clears nothing.
+		assert_noop!(
+			TemplateModule::transfer_claim_v2(Origin::signed(1), 2, vec![0, 1], true),
+			Error::<Test>::NotAuthorized
+		);
+		assert_eq!(TemplateModule::metadata_of(key(&[0, 1])), b"doc".to_vec());
+	});
+}
+
+#[test]
+fn every_event_carries_a_strictly_increasing_sequence_number() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(TemplateModule::set_claim_metadata(Origin::signed(1), vec![0, 1], b"notes".to_vec()));
+		run_to_block(2);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), 2, vec![0, 1]));
+		assert_ok!(TemplateModule::create_claims(Origin::signed(3), vec![vec![1], vec![2]], false));
+		run_to_block(3);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), vec![0, 1]));
+
+		// The sequence number is the first topic of every event of this pallet.
+		let seqs: Vec<u64> = System::events()
+			.into_iter()
+			.filter(|record| matches!(record.event, TestEvent::poe(_)))
+			.map(|record| u64::decode(&mut &record.topics[0].as_bytes()[..8]).unwrap())
+			.collect();
+		assert!(seqs.len() >= 5);
+		assert!(seqs.windows(2).all(|pair| pair[0] < pair[1]));
+
+		// No sequence number is skipped, so indexers can tell when they missed an event.
+		assert_eq!(seqs, (0..seqs.len() as u64).collect::<Vec<_>>());
+		assert_eq!(TemplateModule::claim_seq(), *seqs.last().unwrap() + 1);
+
+		// Claim events also carry their sequence number in their data.
+		let claim_events: Vec<(u64, u64)> = System::events()
+			.into_iter()
+			.filter_map(|record| {
+				let seq = match record.event {
+					TestEvent::poe(RawEvent::ClaimCreated(.., seq))
+					| TestEvent::poe(RawEvent::ClaimTransfered(.., seq))
+					| TestEvent::poe(RawEvent::ClaimRevoked(.., seq)) => seq,
+					_ => return None,
+				};
+				Some((seq, u64::decode(&mut &record.topics[0].as_bytes()[..8]).unwrap()))
+			})
+			.collect();
+		assert_eq!(claim_events.len(), 5);
+		assert!(claim_events.iter().all(|(seq, topic)| seq == topic));
+	});
+}
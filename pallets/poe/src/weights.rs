@@ -0,0 +1,222 @@
+//! Placeholder weights for the proof of existence pallet.
+//!
+//! These numbers are hand-written, not benchmark output: they only follow the storage accesses
+//! of each extrinsic. Replace this file with weights generated on reference hardware by the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! ```text
+//! ./target/release/node-template benchmark --chain dev --pallet pallet_poe --extrinsic '*' \
+//!     --steps 50 --repeat 20 --output pallets/poe/src/weights.rs
+//! ```
+
+use frame_support::weights::{Weight, constants::RocksDbWeight};
+
+impl crate::WeightInfo for () {
+    fn create_claim(l: u32) -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(17 as Weight))
+    }
+    fn revoke_claim(l: u32) -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(27 as Weight))
+    }
+    fn transfer_claim(l: u32) -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
+    }
+    fn set_claim_metadata(l: u32, m: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add((1_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn propose_transfer(l: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn accept_transfer(l: u32) -> Weight {
+        (75_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
+    }
+    fn cancel_transfer(l: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn reclaim_expired(l: u32) -> Weight {
+        (95_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(35 as Weight))
+    }
+    fn set_operator() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_paused() -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn add_to_allowlist() -> Weight {
+        (15_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn remove_from_allowlist() -> Weight {
+        (15_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn force_create(l: u32) -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+    }
+    fn revoke_expired(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((40_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((25 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn rename_claim(l: u32) -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(22 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(39 as Weight))
+    }
+    fn revoke_all(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((40_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((26 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn lock_claim(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn extend_expiry(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn set_auto_renew(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn touch_claim(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn challenge_claim(l: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn resolve_challenge(l: u32) -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(29 as Weight))
+    }
+    fn withdraw_challenge(l: u32) -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn freeze_claim(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn unfreeze_claim(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn add_owner(l: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn remove_owner(l: u32) -> Weight {
+        (75_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
+    }
+    fn escrow_claim(l: u32) -> Weight {
+        (65_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
+    }
+    fn release_claim(l: u32) -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(15 as Weight))
+    }
+    fn set_alias(l: u32, a: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add((1_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn revoke_if_expired(l: u32) -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(27 as Weight))
+    }
+    fn create_claims(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((14 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((16 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_claims(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((72_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((15 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn seal_claim(l: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+}
@@ -7,10 +7,11 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
+use codec::Encode;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, ModuleId, MultiSignature,
+	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority},
 };
 use sp_runtime::traits::{
 	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
@@ -96,10 +97,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 2,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -267,8 +268,113 @@ impl pallet_template::Trait for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MinClaimLength: u32 = 1;
+	pub const MaxClaimLength: u32 = 256;
+	pub const MaxClaimsPerAccount: u32 = 1024;
+	pub const MaxClaimsPerBlock: u32 = 16;
+	pub const MaxCreatedPerBlock: u32 = 1_024;
+	// Anyone may create claims. Set to `true` for a registry of allowlisted notaries.
+	pub const RequireAllowlist: bool = false;
+	// Claims may be up to `MaxClaimLength` bytes, so keep them out of events.
+	pub const EmitHashedEventsOnly: bool = true;
+	// Claims are usually document hashes, where case and trailing zeros are significant.
+	pub const NormalizeKeys: bool = false;
+	pub const ClaimDeposit: Balance = 10_000;
+	// At least the existential deposit, so the first fee can create the treasury account.
+	pub const FeeBase: Balance = 1_000;
+	// Each claim on chain adds a tenth of a percent of the base fee.
+	pub const FeeSlope: Balance = 1;
+	pub const CleanupReward: Balance = 1_000;
+	pub const GracePeriod: BlockNumber = DAYS;
+	pub const LateRevokePenalty: Perbill = Perbill::from_percent(10);
+	// The module id used by `pallet_treasury`, so fees land in its account once it is added.
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const EscrowModuleId: ModuleId = ModuleId(*b"py/poesc");
+	pub const MaxExpiredPerBlock: u32 = 50;
+	pub const MaxMigrationsPerBlock: u32 = 1_000;
+	pub const MaxTotalProofs: u32 = 1_000_000;
+	pub const NearCapacityThreshold: u32 = 10_000;
+	pub const MaxHistoryLength: u32 = 32;
+	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxClaimVersion: u16 = 1;
+	pub const MaxUriLength: u32 = 256;
+	pub const MaxReasonLength: u32 = 128;
+	pub const MaxAliasLength: u32 = 32;
+	pub const MaxRevokeAll: u32 = 100;
+	// No more than `MaxClaimsPerBlock`, so that a full batch can be created in one block.
+	pub const MaxBatch: u32 = 16;
+	pub const MaxQueryBatch: u32 = 1_000;
+	// Sealed claims can still be revoked, so a seal never locks a deposit away for good.
+	pub const SealBlocksRevoke: bool = false;
+	pub const MaxOwners: u32 = 10;
+	pub const MinClaimAge: BlockNumber = HOURS;
+	pub const ChallengeBond: Balance = 100_000;
+	pub const ChallengePeriod: BlockNumber = 7 * DAYS;
+	// Claims may be set to expire at most a year from now.
+	pub const MaxExpiry: BlockNumber = 365 * DAYS;
+	pub const PoeUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+/// Configure the proof of existence pallet in pallets/poe.
+///
+/// The pallet's `Config<T>` part in `construct_runtime!` exposes its `GenesisConfig` as
+/// `PoeModuleConfig`; claims to seed at genesis are listed in its `initial_claims` field in
+/// `node/src/chain_spec.rs`.
 impl pallet_poe::Trait for Runtime {
 	type Event = Event;
+	type MinClaimLength = MinClaimLength;
+	type MaxClaimLength = MaxClaimLength;
+	type MaxClaimsPerAccount = MaxClaimsPerAccount;
+	type MaxClaimsPerBlock = MaxClaimsPerBlock;
+	type MaxCreatedPerBlock = MaxCreatedPerBlock;
+	type EmitHashedEventsOnly = EmitHashedEventsOnly;
+	type NormalizeKeys = NormalizeKeys;
+	type RequireAllowlist = RequireAllowlist;
+	type Currency = Balances;
+	type ClaimDeposit = ClaimDeposit;
+	type FeeBase = FeeBase;
+	type FeeSlope = FeeSlope;
+	type CleanupReward = CleanupReward;
+	type GracePeriod = GracePeriod;
+	type LateRevokePenalty = LateRevokePenalty;
+	type TreasuryModuleId = TreasuryModuleId;
+	type MaxExpiredPerBlock = MaxExpiredPerBlock;
+	type MaxMigrationsPerBlock = MaxMigrationsPerBlock;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxClaimVersion = MaxClaimVersion;
+	type MaxUriLength = MaxUriLength;
+	type MaxReasonLength = MaxReasonLength;
+	type MaxAliasLength = MaxAliasLength;
+	type MaxTotalProofs = MaxTotalProofs;
+	type NearCapacityThreshold = NearCapacityThreshold;
+	type MaxHistoryLength = MaxHistoryLength;
+	type ChallengeBond = ChallengeBond;
+	type ChallengePeriod = ChallengePeriod;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type ResolveOrigin = frame_system::EnsureRoot<AccountId>;
+	type EscrowModuleId = EscrowModuleId;
+	type EscrowOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxExpiry = MaxExpiry;
+	type MaxRevokeAll = MaxRevokeAll;
+	type MaxBatch = MaxBatch;
+	type MaxQueryBatch = MaxQueryBatch;
+	type SealBlocksRevoke = SealBlocksRevoke;
+	type MaxOwners = MaxOwners;
+	type MinClaimAge = MinClaimAge;
+	type UnsignedPriority = PoeUnsignedPriority;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	// Placeholder weights until generated ones replace `pallets/poe/src/weights.rs`.
+	type WeightInfo = ();
+}
+
+/// Let pallets submit unsigned transactions from their off-chain workers.
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -288,7 +394,7 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
 		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
-		PoeModule: pallet_poe::{Module, Call, Storage, Event<T>},
+		PoeModule: pallet_poe::{Module, Call, Storage, Config<T>, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -310,6 +416,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
+	pallet_poe::CheckClaimLength<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>
 );
 /// Unchecked extrinsic type as expected by this runtime.
@@ -449,6 +556,73 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber> for Runtime {
+		fn get_claim(claim: Vec<u8>) -> Option<(AccountId, BlockNumber)> {
+			PoeModule::get_claim(&claim)
+		}
+
+		fn get_claims(claims: Vec<Vec<u8>>) -> Result<Vec<Option<(AccountId, BlockNumber)>>, u8> {
+			PoeModule::get_claims(claims)
+		}
+
+		fn claim_status(claim: Vec<u8>, who: AccountId) -> pallet_poe::ClaimStatus<AccountId> {
+			PoeModule::claim_status(&claim, &who)
+		}
+
+		fn claims_of_paged(
+			owner: AccountId,
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<Vec<u8>>, Option<Vec<u8>>) {
+			PoeModule::claims_of_paged(&owner, start_key, limit)
+		}
+
+		fn claims_in_range(from: BlockNumber, to: BlockNumber, limit: u32) -> (Vec<Vec<u8>>, Option<BlockNumber>) {
+			let (claims, next_block) = PoeModule::claims_in_range(from, to, limit);
+			(claims.iter().map(Encode::encode).collect(), next_block)
+		}
+
+		fn derive_key(claim: Vec<u8>) -> Vec<u8> {
+			PoeModule::derive_key(&claim).encode()
+		}
+
+		fn is_owner(claim: Vec<u8>, who: AccountId) -> bool {
+			PoeModule::is_owner(&claim, &who)
+		}
+
+		fn can_create(claim: Vec<u8>, who: AccountId) -> Result<(), u8> {
+			PoeModule::can_create(&claim, &who)
+		}
+
+		fn claim_details(claim: Vec<u8>) -> Option<pallet_poe::ClaimDetails<AccountId, BlockNumber>> {
+			PoeModule::claim_details(&claim)
+		}
+
+		fn claim_header(claim: Vec<u8>) -> Option<pallet_poe::ClaimHeader> {
+			PoeModule::claim_header(&claim)
+		}
+
+		fn claim_age(claim: Vec<u8>) -> Option<BlockNumber> {
+			PoeModule::claim_age(&claim, System::block_number())
+		}
+
+		fn total_proofs() -> u32 {
+			PoeModule::total_proofs()
+		}
+
+		fn peak_proofs() -> u32 {
+			PoeModule::peak_proofs()
+		}
+
+		fn claim_count_of(owner: AccountId) -> u32 {
+			PoeModule::claim_count_of(owner)
+		}
+
+		fn ownership_distribution(buckets: u32) -> Vec<u32> {
+			PoeModule::ownership_distribution(buckets)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -478,6 +652,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_poe, PoeModule);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)